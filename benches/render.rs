@@ -0,0 +1,27 @@
+// cargo bench --features bench
+#![feature(test)]
+extern crate test;
+
+use chiropterm::__bench::BenchFrame;
+use test::Bencher;
+
+// 256x256 cells is a 2048x2048 buffer, which is about as big as anyone gets
+const SIZE: u16 = 256;
+
+#[bench]
+fn legacy(b: &mut Bencher) {
+    let mut frame = BenchFrame::new(SIZE, SIZE);
+    b.iter(|| { frame.legacy(); test::black_box(frame.buffer()); });
+}
+
+#[bench]
+fn rows(b: &mut Bencher) {
+    let mut frame = BenchFrame::new(SIZE, SIZE);
+    b.iter(|| { frame.rows(1); test::black_box(frame.buffer()); });
+}
+
+#[bench]
+fn rows_4_threads(b: &mut Bencher) {
+    let mut frame = BenchFrame::new(SIZE, SIZE);
+    b.iter(|| { frame.rows(4); test::black_box(frame.buffer()); });
+}
@@ -0,0 +1,113 @@
+use euclid::{Rect, size2};
+
+use crate::{Brush, Interactor, aliases::*};
+
+use super::{BoxArt, BoxStyle};
+
+pub struct Frame<'t> {
+    pub double_border: bool,
+    pub style: BoxStyle,
+    pub title: Option<&'t str>,
+    pub close: Option<Interactor>,
+
+    // in characters, relative to the top left of the frame
+    pub h_dividers: Vec<isize>,
+    pub v_dividers: Vec<isize>,
+}
+
+impl<'t> Default for Frame<'t> {
+    fn default() -> Self { Frame::new() }
+}
+
+impl<'t> Frame<'t> {
+    pub fn new() -> Frame<'t> {
+        Frame {
+            double_border: false,
+            style: BoxStyle::Lines,
+            title: None,
+            close: None,
+            h_dividers: vec![],
+            v_dividers: vec![],
+        }
+    }
+
+    pub fn double(mut self) -> Self {
+        self.double_border = true;
+        self
+    }
+
+    pub fn style(mut self, style: BoxStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn title(mut self, title: &'t str) -> Self {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn close(mut self, interactor: Interactor) -> Self {
+        self.close = Some(interactor);
+        self
+    }
+
+    pub fn h_divider(mut self, y: isize) -> Self {
+        self.h_dividers.push(y);
+        self
+    }
+
+    pub fn v_divider(mut self, x: isize) -> Self {
+        self.v_dividers.push(x);
+        self
+    }
+}
+
+impl <'a> Brush<'a> {
+    /// Draw `frame` around the edge of this brush, returning a brush for the inside.
+    pub fn draw_frame(&self, frame: &Frame) -> Brush<'a> {
+        let sz = self.font.char_size();
+        let me = self.rect();
+        // BoxArt works in characters, not cells
+        let chars = Rect::new(
+            point2(me.min_x() / sz.width, me.min_y() / sz.height),
+            size2(me.size.width / sz.width, me.size.height / sz.height),
+        );
+        let origin = point2(chars.min_x() * sz.width, chars.min_y() * sz.height);
+
+        let mut boxart = BoxArt::new();
+        boxart.style = frame.style;
+        boxart.draw_box(chars, frame.double_border);
+        // dividers are single so they meet double frames as ╟ and ╤
+        for y in frame.h_dividers.iter() {
+            boxart.draw_hline(point2(chars.min_x(), chars.min_y() + y), chars.width(), false);
+        }
+        for x in frame.v_dividers.iter() {
+            boxart.draw_vline(point2(chars.min_x() + x, chars.min_y()), chars.height(), false);
+        }
+        boxart.draw(self);
+
+        // leave room for a corner and a line on either side
+        let room = chars.width() - 4;
+        if let Some(title) = frame.title {
+            let close_room = if frame.close.is_some() { 2 } else { 0 };
+            let title: String = title.chars().take((room - close_room - 2).max(0) as usize).collect();
+            if !title.is_empty() {
+                self.at(origin + vec2(2 * sz.width, 0)).putfs(&format!(" {} ", title));
+            }
+        }
+
+        if let Some(close) = frame.close {
+            if room >= 1 {
+                self.at(origin + vec2((chars.width() - 3) * sz.width, 0))
+                    .interactor(close, (255, 255))
+                    .putch(b'x');
+            }
+        }
+
+        self.region(rect(
+            origin.x + sz.width, origin.y + sz.height,
+            ((chars.width() - 2) * sz.width).max(0),
+            ((chars.height() - 2) * sz.height).max(0),
+        ))
+    }
+}
@@ -1,9 +1,17 @@
+mod frame;
+mod style;
+
+use euclid::size2;
 use gridd_euclid::{CopyEndlessGrid, PointsIn};
 
 use crate::{Brush, aliases::*};
 
+pub use frame::Frame;
+pub use style::BoxStyle;
+
 pub struct BoxArt {
     content: CopyEndlessGrid<u8, CellSpace>,
+    pub style: BoxStyle,
 }
 
 enum BoxSide {
@@ -14,6 +22,7 @@ impl BoxArt {
     pub fn new() -> BoxArt {
         BoxArt {
             content: CopyEndlessGrid::new(0),
+            style: BoxStyle::Lines,
         }
     }
 
@@ -23,7 +32,7 @@ impl BoxArt {
         let sz_y = sz.height;
 
         for xy in isize::points_in(self.content.rect()) {
-            if let Some(bc) = Self::box_char(self.content.get(xy)) {
+            if let Some(bc) = self.style.glyph(self.content.get(xy), xy) {
                 brush.at(point2(xy.x * sz_x, xy.y * sz_y)).putch(bc as u16);
             }
         }
@@ -78,6 +87,14 @@ impl BoxArt {
         }
     }
 
+    pub fn draw_hline(&mut self, start: CellPoint, length: isize, double_border: bool) {
+        self.draw_box(CellRect::new(start, size2(length, 1)), double_border)
+    }
+
+    pub fn draw_vline(&mut self, start: CellPoint, length: isize, double_border: bool) {
+        self.draw_box(CellRect::new(start, size2(1, length)), double_border)
+    }
+
     fn add(&mut self, at: CellPoint, side: BoxSide, double_border: bool) {
         let norm_side = 3 - side as u8;
         let existing = self.content.get(at);
@@ -94,130 +111,97 @@ impl BoxArt {
         // 01: single line
         // 10: double line
         // 11: double line
-        // this bit fuckery makes sure that 2 becomes 3
-        Some(match mask | ((mask & 0b10101010) >> 1) {
-            0b00_00_00_00 => return None,
-            0b01_00_00_00 => 0xc1,  // optional case
-            0b00_00_01_00 => 0xc2,  // optional case
-            0b01_00_01_00 |
-            0b11_00_01_00 |
-            0b01_00_11_00 =>
-                0xb3,
-            0b01_00_01_01 =>
-                0xb4,
-            0b01_00_01_11 =>
-                0xb5,
-            0b11_00_11_01 |
-            0b01_00_11_01 |
-            0b11_00_01_01 =>
-                0xb6,
-            0b00_00_11_01 =>
-                0xb7,
-            0b00_00_01_11 =>
-                0xb8,
-            0b11_00_11_11 |
-            0b01_00_11_11 |
-            0b11_00_01_11 =>
-                0xb9,
-            0b11_00_00_00 => 0xd0,  // optional case
-            0b00_00_11_00 => 0xd2,  // optional case
-            0b11_00_11_00 =>
-                0xba,
-            0b00_00_11_11 =>
-                0xbb,
-            0b11_00_00_11 =>
-                0xbc,
-            0b11_00_00_01 =>
-                0xbd,
-            0b01_00_00_11 =>
-                0xbe,
-            0b00_00_01_01 =>
-                0xbf,
-            0b01_01_00_00 =>
-                0xc0,
-            0b01_01_00_01 |
-            0b01_11_00_01 |
-            0b01_01_00_11 =>
-                0xc1,
-            0b00_01_01_01 |
-            0b00_11_01_01 |
-            0b00_01_01_11 =>
-                0xc2,
-            0b01_01_01_00 =>
-                0xc3,
-            0b00_01_00_00 => 0xc3,  // optional case
-            0b00_00_00_01 => 0xb4,  // optional case
-            0b00_01_00_01 |
-            0b00_11_00_01 |
-            0b00_01_00_11 =>
-                0xc4,
-            0b01_01_01_01 |
-            0b11_01_01_01 |
-            0b01_11_01_01 |
-            0b01_01_11_01 |
-            0b01_01_01_11 |
-            0b11_11_01_01 |
-            0b01_11_11_01 |
-            0b01_01_11_11 |
-            0b11_01_01_11 =>
-                0xc5,
-            0b01_11_01_00 =>
-                0xc6,
-            0b11_01_11_00 |
-            0b01_01_11_00 |
-            0b11_01_01_00 =>
-                0xc7,
-            0b11_11_00_00 =>
-                0xc8,
-            0b00_11_11_00 =>
-                0xc9,
-            0b11_11_00_11 |
-            0b11_01_00_11 |
-            0b11_11_00_01 =>
-                0xca,
-            0b00_11_11_11 |
-            0b00_01_11_11 |
-            0b00_11_11_01 =>
-                0xcb,
-            0b11_11_11_00 |
-            0b01_11_11_00 |
-            0b11_11_01_00 =>
-                0xcc,
-            0b00_11_00_00 => 0xc6,  // optional case
-            0b00_00_00_11 => 0xb5,  // optional case
-            0b00_11_00_11 =>
-                0xcd,
-            0b11_11_11_11 |
-            0b01_11_11_11 |
-            0b11_01_11_11 |
-            0b11_11_01_11 |
-            0b11_11_11_01 =>
-                0xce,
-            0b01_11_00_11 =>
-                0xcf,
-            0b11_01_00_01 =>
-                0xd0,
-            0b00_11_01_11 =>
-                0xd1,
-            0b00_01_11_01 =>
-                0xd2,
-            0b11_01_00_00 =>
-                0xd3,
-            0b01_11_00_00 =>
-                0xd4,
-            0b00_11_01_00 =>
-                0xd5,
-            0b00_01_11_00 =>
-                0xd6,
-            0b11_01_11_01 =>
-                0xd7,
-            0b01_11_01_11 =>
-                0xd8,
-            0b01_00_00_01 =>
-                0xd9,
-            0b00_01_01_00 =>
-                0xda,
-            _ => unreachable!("confused about mask: {}", mask)
-        })
+        //
+        // where single and double lines overlap, double wins
+        let mut want = style::arms(mask);
+        let n_arms = want.iter().filter(|a| **a != 0).count();
+        if n_arms == 0 { return None }
+
+        // a lone arm is the end of a line: draw it straight
+        if n_arms == 1 {
+            for i in 0..4 {
+                if want[i] != 0 { want[(i + 2) % 4] = want[i] }
+            }
+        }
+
+        // CP437 has every combination of single arms, but only some mixes of single and double,
+        // so find the glyph with the right arms that gets the fewest weights wrong.
+        // turning a double line single is worse than the reverse
+        let cost = |have: &[u8; 4]| -> Option<u32> {
+            let mut total = 0;
+            for (w, h) in want.iter().zip(have.iter()) {
+                if (*w == 0) != (*h == 0) { return None }
+                if w > h { total += 2 }
+                if w < h { total += 1 }
+            }
+            Some(total)
+        };
+
+        BOX_GLYPHS.iter()
+            .filter_map(|(glyph, have)| cost(have).map(|c| (c, *glyph)))
+            .min_by_key(|(c, _)| *c)
+            .map(|(_, glyph)| glyph)
+    }
+}
+
+// glyph, then arms clockwise from north (0: none, 1: single, 2: double)
+const BOX_GLYPHS: [(u8, [u8; 4]); 40] = [
+    (0xb3, [1, 0, 1, 0]), (0xba, [2, 0, 2, 0]),
+    (0xc4, [0, 1, 0, 1]), (0xcd, [0, 2, 0, 2]),
+
+    (0xc0, [1, 1, 0, 0]), (0xc8, [2, 2, 0, 0]), (0xd3, [2, 1, 0, 0]), (0xd4, [1, 2, 0, 0]),
+    (0xda, [0, 1, 1, 0]), (0xc9, [0, 2, 2, 0]), (0xd5, [0, 2, 1, 0]), (0xd6, [0, 1, 2, 0]),
+    (0xbf, [0, 0, 1, 1]), (0xbb, [0, 0, 2, 2]), (0xb7, [0, 0, 2, 1]), (0xb8, [0, 0, 1, 2]),
+    (0xd9, [1, 0, 0, 1]), (0xbc, [2, 0, 0, 2]), (0xbd, [2, 0, 0, 1]), (0xbe, [1, 0, 0, 2]),
+
+    (0xc3, [1, 1, 1, 0]), (0xcc, [2, 2, 2, 0]), (0xc6, [1, 2, 1, 0]), (0xc7, [2, 1, 2, 0]),
+    (0xb4, [1, 0, 1, 1]), (0xb9, [2, 0, 2, 2]), (0xb5, [1, 0, 1, 2]), (0xb6, [2, 0, 2, 1]),
+    (0xc2, [0, 1, 1, 1]), (0xcb, [0, 2, 2, 2]), (0xd1, [0, 2, 1, 2]), (0xd2, [0, 1, 2, 1]),
+    (0xc1, [1, 1, 0, 1]), (0xca, [2, 2, 0, 2]), (0xcf, [1, 2, 0, 2]), (0xd0, [2, 1, 0, 1]),
+
+    (0xc5, [1, 1, 1, 1]), (0xce, [2, 2, 2, 2]), (0xd8, [1, 2, 1, 2]), (0xd7, [2, 1, 2, 1]),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // arms clockwise from north: 0 for none, 1 for single, 2 for double
+    fn mask(arms: [u8; 4]) -> u8 {
+        arms.iter().fold(0, |m, a| m << 2 | match a { 0 => 0b00, 1 => 0b01, _ => 0b10 })
+    }
+
+    #[test]
+    fn lone_arms_draw_straight() {
+        let table = [
+            ([1, 0, 0, 0], 0xb3), ([0, 0, 1, 0], 0xb3), ([2, 0, 0, 0], 0xba),
+            ([0, 1, 0, 0], 0xc4), ([0, 0, 0, 1], 0xc4), ([0, 0, 0, 2], 0xcd),
+        ];
+        for (arms, glyph) in table {
+            assert_eq!(BoxArt::box_char(mask(arms)), Some(glyph), "{:?}", arms);
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn mixed_weights_pick_the_closest_glyph() {
+        let table = [
+            // exact glyphs exist
+            ([2, 1, 0, 0], 0xd3), ([0, 2, 1, 0], 0xd5), ([1, 2, 1, 2], 0xd8), ([2, 1, 2, 1], 0xd7),
+            // they don't: keep the doubles double where possible
+            ([2, 1, 1, 0], 0xc7), ([1, 2, 2, 2], 0xce), ([0, 1, 2, 2], 0xcb),
+        ];
+        for (arms, glyph) in table {
+            assert_eq!(BoxArt::box_char(mask(arms)), Some(glyph), "{:?}", arms);
+        }
+    }
+
+    #[test]
+    fn every_mask_resolves() {
+        assert_eq!(BoxArt::box_char(0), None);
+        for m in 1..=255u8 {
+            assert!(BoxArt::box_char(m).is_some(), "{:08b}", m);
+        }
+        // both bits set counts as double
+        assert_eq!(BoxArt::box_char(0b11_00_11_00), BoxArt::box_char(mask([2, 0, 2, 0])));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::aliases::*;
+
+use super::BoxArt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoxStyle {
+    Lines,  // CP437 single and double lines
+    Block,  // solid blocks
+    Rounded,  // lines, but corners are . and '
+    Dashed,  // lines, with every other straight cell left out
+    Ascii,  // + - = |
+}
+
+// arms, clockwise from north: 0 for none, 1 for single, 2 for double
+pub(super) fn arms(mask: u8) -> [u8; 4] {
+    let arm = |shift: u8| match mask >> shift & 0b11 {
+        0b00 => 0,
+        0b01 => 1,
+        _ => 2,
+    };
+    [arm(6), arm(4), arm(2), arm(0)]
+}
+
+impl BoxStyle {
+    pub(super) fn glyph(&self, mask: u8, at: CellPoint) -> Option<u8> {
+        if mask == 0 { return None }
+        let [n, e, s, w] = arms(mask);
+        let vertical = (n != 0 || s != 0) && e == 0 && w == 0;
+        let horizontal = (e != 0 || w != 0) && n == 0 && s == 0;
+
+        match self {
+            BoxStyle::Lines => BoxArt::box_char(mask),
+            BoxStyle::Block => Some(0xdb),
+            BoxStyle::Rounded => match (n != 0, e != 0, s != 0, w != 0) {
+                (false, true, true, false) | (false, false, true, true) => Some(b'.'),
+                (true, true, false, false) | (true, false, false, true) => Some(b'\''),
+                _ => BoxArt::box_char(mask),
+            },
+            BoxStyle::Dashed => {
+                if (vertical || horizontal) && (at.x + at.y).rem_euclid(2) == 1 { return None }
+                BoxArt::box_char(mask)
+            }
+            BoxStyle::Ascii => {
+                if vertical { Some(b'|') }
+                else if horizontal { Some(if e == 2 || w == 2 { b'=' } else { b'-' }) }
+                else { Some(b'+') }
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use euclid::{Rect, size2};
 
-use crate::{BoxArt, Brush};
+use crate::{BoxArt, BoxStyle, Brush, PixelCanvas, SubCell};
 
 impl <'a> Brush<'a> {
     pub fn draw_box(&self, double_border: bool) {
+        self.draw_box_styled(double_border, BoxStyle::Lines)
+    }
+
+    pub fn draw_box_styled(&self, double_border: bool, style: BoxStyle) {
         let mut boxart = BoxArt::new();
+        boxart.style = style;
         let rect = self.rect();
         let sz = self.font.char_size();
         let rect = Rect::new(
@@ -23,4 +28,10 @@ impl <'a> Brush<'a> {
         f(&mut boxart);
         boxart.draw(self);
     }
+
+    pub fn draw_pixels(&self, sub_cell: SubCell, f: impl FnOnce(&mut PixelCanvas)) {
+        let mut canvas = PixelCanvas::new(sub_cell);
+        f(&mut canvas);
+        canvas.draw(self);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use gridd_euclid::PointsIn;
+
+use crate::{Brush, Brushable, FSem, SemanticContent};
+use crate::aliases::*;
+
+#[derive(Clone, Copy, Debug)]
+pub enum GradientShape {
+    Horizontal,  // ramp[0] on the left
+    Vertical,  // ramp[0] on the top
+    Radial,  // ramp[0] in the center
+}
+
+// 4x4 bayer matrix, scaled to 0..16
+const BAYER: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+// ░▒▓, from least to most coverage
+const SHADES: [u16; 3] = [0xb0, 0xb1, 0xb2];
+
+impl <'a> Brush<'a> {
+    /// Fill with a dithered transition through `ramp`, which can be two
+    /// palette indices or a whole ramp like `colors::DkRed`.
+    pub fn fill_gradient(&self, shape: GradientShape, ramp: &[u8]) {
+        let r = self.rect;
+        let center_x = (r.min_x() + r.max_x()) as f32 / 2.0;
+        let center_y = (r.min_y() + r.max_y()) as f32 / 2.0;
+        let max_dist = (r.width() as f32 / 2.0).hypot(r.height() as f32 / 2.0);
+
+        for xy in isize::points_in(r) {
+            let t = match shape {
+                GradientShape::Horizontal =>
+                    (xy.x - r.min_x()) as f32 / (r.width() - 1).max(1) as f32,
+                GradientShape::Vertical =>
+                    (xy.y - r.min_y()) as f32 / (r.height() - 1).max(1) as f32,
+                GradientShape::Radial => {
+                    // sample at the middle of the cell
+                    let dx = xy.x as f32 + 0.5 - center_x;
+                    let dy = xy.y as f32 + 0.5 - center_y;
+                    dx.hypot(dy) / max_dist.max(1.0)
+                }
+            };
+            self.draw(xy, dither(ramp, t, xy));
+        }
+    }
+
+    /// Fill with a single point `t` (0.0 to 1.0) along `ramp`, dithered.
+    pub fn fill_dithered(&self, ramp: &[u8], t: f32) {
+        for xy in isize::points_in(self.rect) {
+            self.draw(xy, dither(ramp, t, xy))
+        }
+    }
+}
+
+fn dither(ramp: &[u8], t: f32, at: CellPoint) -> FSem {
+    if ramp.is_empty() { return FSem::new() }
+    if ramp.len() == 1 { return FSem::new().sem(SemanticContent::Blank).bg(ramp[0]) }
+
+    let pos = t.clamp(0.0, 1.0) * (ramp.len() - 1) as f32;
+    let ix = (pos as usize).min(ramp.len() - 2);
+    let (lo, hi) = (ramp[ix], ramp[ix + 1]);
+
+    // five levels between lo and hi: solid, three shades, solid
+    let level_f = (pos - ix as f32) * 4.0;
+    let mut level = level_f as usize;
+    let threshold = BAYER[at.y.rem_euclid(4) as usize][at.x.rem_euclid(4) as usize] as f32 / 16.0;
+    if level_f - level as f32 > threshold { level += 1 }
+
+    match level {
+        0 => FSem::new().sem(SemanticContent::Blank).bg(lo),
+        1..=3 => FSem::new().sem(SemanticContent::Small(SHADES[level - 1])).color((lo, hi)),
+        _ => FSem::new().sem(SemanticContent::Blank).bg(hi),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gridd_euclid::PointsIn;
+
+    use super::*;
+
+    fn shade(f: FSem) -> Option<u16> {
+        match f.sem { Some(SemanticContent::Small(g)) => Some(g), _ => None }
+    }
+
+    #[test]
+    fn ends_are_solid() {
+        for xy in isize::points_in(rect(0, 0, 4, 4)) {
+            let start = dither(&[1, 2, 3], 0.0, xy);
+            assert!(matches!(start.sem, Some(SemanticContent::Blank)) && start.bg == Some(1));
+            let end = dither(&[1, 2, 3], 1.0, xy);
+            assert!(matches!(end.sem, Some(SemanticContent::Blank)) && end.bg == Some(3));
+        }
+    }
+
+    #[test]
+    fn thresholds_split_a_bayer_tile_evenly() {
+        // exactly on a level: no dithering at all
+        for xy in isize::points_in(rect(0, 0, 4, 4)) {
+            assert_eq!(shade(dither(&[1, 2], 0.5, xy)), Some(0xb1));
+        }
+
+        // halfway between two levels: half the tile rounds up
+        let glyphs: Vec<_> = isize::points_in(rect(0, 0, 4, 4)).map(|xy| shade(dither(&[1, 2], 0.625, xy))).collect();
+        assert_eq!(glyphs.iter().filter(|g| **g == Some(0xb1)).count(), 8);
+        assert_eq!(glyphs.iter().filter(|g| **g == Some(0xb2)).count(), 8);
+
+        // the pattern repeats every four cells, negative coordinates included
+        for xy in isize::points_in(rect(-4, -4, 4, 4)) {
+            assert_eq!(shade(dither(&[1, 2], 0.625, xy)), shade(dither(&[1, 2], 0.625, xy + vec2(4, 4))));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::Brush;
+use crate::aliases::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    Fixed(isize),
+    Percent(isize),  // of the space left after padding and gutters
+    Fraction(isize),  // a weighted share of whatever Fixed and Percent don't take
+    Min(isize),  // like Fraction(1), but never smaller than this
+    Max(isize),  // like Fraction(1), but never bigger than this
+}
+
+// Layouts are solved against the brush's rect every time you call them,
+// so if you lay out inside on_redraw they follow the terminal as it resizes.
+#[derive(Clone, Debug)]
+pub struct Layout {
+    pub constraints: Vec<Constraint>,
+    pub gutter: isize,
+    pub padding: isize,
+}
+
+impl Layout {
+    pub fn new(constraints: &[Constraint]) -> Layout {
+        Layout { constraints: constraints.to_vec(), gutter: 0, padding: 0 }
+    }
+
+    pub fn gutter(mut self, gutter: isize) -> Layout {
+        self.gutter = gutter;
+        self
+    }
+
+    pub fn padding(mut self, padding: isize) -> Layout {
+        self.padding = padding;
+        self
+    }
+
+    /// (offset, size) of every slot, along a line `total` cells long
+    pub fn solve(&self, total: isize) -> Vec<(isize, isize)> {
+        let n = self.constraints.len() as isize;
+        if n == 0 { return vec![] }
+
+        let available = (total - 2 * self.padding - self.gutter * (n - 1)).max(0);
+        let mut sizes: Vec<Option<isize>> = self.constraints.iter().map(|c| match c {
+            Constraint::Fixed(x) => Some((*x).max(0)),
+            Constraint::Percent(p) => Some((available * *p / 100).max(0)),
+            _ => None,
+        }).collect();
+
+        // hand out the rest by weight, pinning anything that breaks its bound and retrying
+        loop {
+            let taken: isize = sizes.iter().flatten().sum();
+            let left = (available - taken).max(0);
+            let weight = |c: &Constraint| match c {
+                Constraint::Fraction(w) => (*w).max(0),
+                _ => 1,
+            };
+            let total_weight: isize = self.constraints.iter().zip(sizes.iter())
+                .filter(|(_, s)| s.is_none())
+                .map(|(c, _)| weight(c))
+                .sum();
+            if total_weight == 0 { break }
+
+            let mut pinned_any = false;
+            for (c, s) in self.constraints.iter().zip(sizes.iter_mut()) {
+                if s.is_some() { continue }
+                let share = left * weight(c) / total_weight;
+                match c {
+                    Constraint::Min(m) if share < *m => { *s = Some(*m); pinned_any = true }
+                    Constraint::Max(m) if share > *m => { *s = Some((*m).max(0)); pinned_any = true }
+                    _ => {}
+                }
+            }
+            if pinned_any { continue }
+
+            // nothing pinned: the shares stand. rounding leftovers go to the first flexible slots
+            let mut spare = left;
+            let mut flexible = vec![];
+            for (i, (c, s)) in self.constraints.iter().zip(sizes.iter_mut()).enumerate() {
+                if s.is_some() { continue }
+                let share = left * weight(c) / total_weight;
+                *s = Some(share);
+                spare -= share;
+                if weight(c) > 0 { flexible.push(i) }
+            }
+            for i in flexible.into_iter().take(spare.max(0) as usize) {
+                sizes[i] = sizes[i].map(|x| x + 1);
+            }
+            break
+        }
+
+        // if the fixed parts don't fit, the last slots lose out
+        let mut offset = self.padding;
+        let end = self.padding + available + self.gutter * (n - 1);
+        let mut out = vec![];
+        for s in sizes {
+            let size = s.unwrap_or(0).min((end - offset).max(0));
+            out.push((offset, size));
+            offset = (offset + size + self.gutter).min(end);
+        }
+        out
+    }
+}
+
+impl <'a> Brush<'a> {
+    pub fn rows(&self, layout: &Layout) -> Vec<Brush<'a>> {
+        let me = self.rect();
+        layout.solve(me.height()).into_iter()
+            .map(|(y, h)| self.region(rect(me.min_x() + layout.padding, me.min_y() + y, (me.width() - 2 * layout.padding).max(0), h)))
+            .collect()
+    }
+
+    pub fn columns(&self, layout: &Layout) -> Vec<Brush<'a>> {
+        let me = self.rect();
+        layout.solve(me.width()).into_iter()
+            .map(|(x, w)| self.region(rect(me.min_x() + x, me.min_y() + layout.padding, w, (me.height() - 2 * layout.padding).max(0))))
+            .collect()
+    }
+
+    /// One `Vec` per row, each with one brush per column.
+    pub fn grid(&self, columns: &Layout, rows: &Layout) -> Vec<Vec<Brush<'a>>> {
+        let me = self.rect();
+        let xs = columns.solve(me.width());
+        let ys = rows.solve(me.height());
+        ys.iter().map(|(y, h)| {
+            xs.iter().map(|(x, w)| self.region(rect(me.min_x() + x, me.min_y() + y, *w, *h))).collect()
+        }).collect()
+    }
+
+    pub fn padded(&self, padding: isize) -> Brush<'a> {
+        let me = self.rect();
+        self.region(rect(
+            me.min_x() + padding, me.min_y() + padding,
+            (me.width() - 2 * padding).max(0), (me.height() - 2 * padding).max(0),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Constraint::*;
+
+    fn sizes(layout: &Layout, total: isize) -> Vec<isize> {
+        layout.solve(total).into_iter().map(|(_, s)| s).collect()
+    }
+
+    #[test]
+    fn fixed_percent_and_fractions() {
+        assert_eq!(sizes(&Layout::new(&[Fixed(10), Fraction(1), Fraction(2)]), 40), vec![10, 10, 20]);
+        assert_eq!(sizes(&Layout::new(&[Percent(25), Fraction(1)]), 40), vec![10, 30]);
+        // rounding leftovers go to the first flexible slots
+        assert_eq!(sizes(&Layout::new(&[Fraction(1), Fraction(1), Fraction(1)]), 10), vec![4, 3, 3]);
+    }
+
+    #[test]
+    fn bounds_are_pinned() {
+        assert_eq!(sizes(&Layout::new(&[Min(8), Fraction(1)]), 10), vec![8, 2]);
+        assert_eq!(sizes(&Layout::new(&[Max(3), Fraction(1)]), 10), vec![3, 7]);
+    }
+
+    #[test]
+    fn gutters_and_padding_come_first() {
+        let layout = Layout::new(&[Fraction(1), Fraction(1)]).gutter(2).padding(1);
+        assert_eq!(layout.solve(12), vec![(1, 4), (7, 4)]);
+    }
+
+    #[test]
+    fn overflow_squeezes_the_last_slots() {
+        assert_eq!(sizes(&Layout::new(&[Fixed(8), Fixed(8)]), 10), vec![8, 2]);
+        assert_eq!(sizes(&Layout::new(&[Fixed(8), Fraction(1)]), 4), vec![4, 0]);
+        assert_eq!(Layout::new(&[]).solve(10), vec![]);
+    }
+}
@@ -4,11 +4,22 @@ mod align;
 mod bevel;
 mod boxart;
 mod fill;
+mod gradient;
+mod layout;
+mod overlay;
+mod shapes;
 mod split;
 
+pub use gradient::GradientShape;
+pub use layout::{Constraint, Layout};
+pub(crate) use shapes::line_points;
+
 
 pub trait Brushable {
     fn draw(&self, at: CellPoint, f: FSem);
+    // read-modify-write: run every color already drawn at `at` through `map`
+    // (a no-op for types that can't read back what they drew)
+    fn remap(&self, _at: CellPoint, _map: &dyn Fn(u8) -> u8) {}
     // TODO: "set cursor" function, can be a no op for types without a cursor
 
     fn brush_at(&self, rect: CellRect) -> Brush<'_> where Self: Sized {
@@ -223,4 +234,14 @@ impl<'a> Brushable for Brush<'a> {
 
         self.underlying.draw(at, f)
     }
+
+    fn remap(&self, mut at: CellPoint, map: &dyn Fn(u8) -> u8) {
+        at += self.cursor_offset;
+
+        if !self.clip.contains(at) { 
+            return; 
+        }
+
+        self.underlying.remap(at, map)
+    }
 }
\ No newline at end of file
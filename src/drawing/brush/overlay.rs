@@ -0,0 +1,36 @@
+use gridd_euclid::PointsIn;
+
+use crate::{Brush, Brushable, ColorMap, colors};
+use crate::aliases::*;
+
+// how far down its ramp a shadow pushes each color
+const SHADOW_STEPS: u8 = 4;
+
+impl <'a> Brush<'a> {
+    /// Run every color already drawn in this brush through `map`.
+    pub fn remap_colors(&self, map: impl Fn(u8) -> u8) {
+        for xy in isize::points_in(self.rect) {
+            self.remap(xy, &map)
+        }
+    }
+
+    pub fn apply_color_map(&self, color_map: &ColorMap) {
+        self.remap_colors(|c| color_map.get(c))
+    }
+
+    /// Darken whatever is already drawn, instead of drawing over it.
+    pub fn dim(&self, steps: u8) {
+        self.remap_colors(|c| colors::darken(c, steps))
+    }
+
+    /// Darken the area this brush's rect would cover if moved by `offset`, 
+    /// minus the rect itself. Call it before drawing the popup on top.
+    // the point is to land outside the brush, so this ignores the clip
+    pub fn shadow(&self, offset: CellVector) {
+        let darken = |c| colors::darken(c, SHADOW_STEPS);
+        for xy in isize::points_in(self.rect.translate(offset)) {
+            if self.rect.contains(xy) { continue }
+            self.underlying.remap(xy + self.cursor_offset, &darken)
+        }
+    }
+}
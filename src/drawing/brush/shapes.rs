@@ -0,0 +1,196 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use euclid::size2;
+use gridd_euclid::PointsIn;
+
+use crate::{Brush, Brushable, FSem};
+use crate::aliases::*;
+use crate::formatting::FChar;
+
+
+impl <'a> Brush<'a> {
+    pub fn line(&self, from: CellPoint, to: CellPoint, f: FSem) {
+        for xy in line_points(from, to) {
+            self.draw(xy, f)
+        }
+    }
+
+    /// Like `line`, but picks a line-drawing glyph for every step.
+    /// Points are in characters of the current font, like `BoxArt`.
+    pub fn line_glyphs(&self, from: CellPoint, to: CellPoint) {
+        let points = line_points(from, to);
+        let sz = self.font.char_size();
+        let mut b = self.clone();
+
+        for i in 0..points.len() {
+            let before = points[i.saturating_sub(1)];
+            let after = points[(i + 1).min(points.len() - 1)];
+            let glyph = slope_glyph(after - before);
+
+            let at = point2(points[i].x * sz.width, points[i].y * sz.height);
+            self.font.draw_char(at, FChar::new().sprite(glyph), &mut b);
+        }
+    }
+
+    pub fn circle(&self, center: CellPoint, radius: isize, f: FSem) {
+        self.ellipse(center, size2(radius, radius), f)
+    }
+
+    pub fn fill_circle(&self, center: CellPoint, radius: isize, f: FSem) {
+        self.fill_ellipse(center, size2(radius, radius), f)
+    }
+
+    pub fn ellipse(&self, center: CellPoint, radii: CellSize, f: FSem) {
+        let inside = |xy: CellPoint| in_ellipse(xy - center, radii);
+        for xy in isize::points_in(ellipse_bounds(center, radii)) {
+            if !inside(xy) { continue }
+
+            // on the outline if any orthogonal neighbor is outside
+            if !inside(xy + vec2(1, 0)) || !inside(xy - vec2(1, 0)) ||
+                !inside(xy + vec2(0, 1)) || !inside(xy - vec2(0, 1)) {
+                self.draw(xy, f)
+            }
+        }
+    }
+
+    pub fn fill_ellipse(&self, center: CellPoint, radii: CellSize, f: FSem) {
+        for xy in isize::points_in(ellipse_bounds(center, radii)) {
+            if in_ellipse(xy - center, radii) {
+                self.draw(xy, f)
+            }
+        }
+    }
+
+    pub fn polygon(&self, points: &[CellPoint], f: FSem) {
+        for i in 0..points.len() {
+            self.line(points[i], points[(i + 1) % points.len()], f)
+        }
+    }
+
+    // even-odd rule, sampled at the center of each cell
+    pub fn fill_polygon(&self, points: &[CellPoint], f: FSem) {
+        if points.len() < 3 { return self.polygon(points, f) }
+
+        let min_y = points.iter().map(|p| p.y).min().unwrap();
+        let max_y = points.iter().map(|p| p.y).max().unwrap();
+
+        for y in min_y..max_y {
+            let sample_y = y as f32 + 0.5;
+            let mut crossings = vec![];
+            for i in 0..points.len() {
+                let p0 = points[i];
+                let p1 = points[(i + 1) % points.len()];
+                let (y0, y1) = (p0.y as f32, p1.y as f32);
+                if (y0 <= sample_y) == (y1 <= sample_y) { continue }
+
+                let t = (sample_y - y0) / (y1 - y0);
+                crossings.push(p0.x as f32 + t * (p1.x - p0.x) as f32);
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            for pair in crossings.chunks(2) {
+                if let [x0, x1] = pair {
+                    for x in (x0 - 0.5).ceil() as isize..(x1 - 0.5).ceil() as isize {
+                        self.draw(point2(x, y), f)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Brushes can't be read back, so the caller says which cells the fill may spread into.
+    /// The fill never leaves `rect()`.
+    pub fn flood_fill(&self, start: CellPoint, f: FSem, passable: impl Fn(CellPoint) -> bool) {
+        let bounds = self.rect();
+        if !bounds.contains(start) || !passable(start) { return }
+
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::new();
+        seen.insert((start.x, start.y));
+        queue.push_back(start);
+
+        while let Some(xy) = queue.pop_front() {
+            self.draw(xy, f);
+
+            for d in [vec2(1, 0), vec2(-1, 0), vec2(0, 1), vec2(0, -1)] {
+                let n = xy + d;
+                if !bounds.contains(n) || seen.contains(&(n.x, n.y)) { continue }
+                seen.insert((n.x, n.y));
+                if passable(n) { queue.push_back(n) }
+            }
+        }
+    }
+}
+
+pub(crate) fn line_points(from: CellPoint, to: CellPoint) -> Vec<CellPoint> {
+    // bresenham
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let sx = if from.x < to.x { 1 } else { -1 };
+    let sy = if from.y < to.y { 1 } else { -1 };
+
+    let mut points = vec![];
+    let mut xy = from;
+    let mut err = dx + dy;
+    loop {
+        points.push(xy);
+        if xy == to { return points }
+
+        let e2 = 2 * err;
+        if e2 >= dy { err += dy; xy.x += sx; }
+        if e2 <= dx { err += dx; xy.y += sy; }
+    }
+}
+
+fn slope_glyph(d: CellVector) -> u16 {
+    let (ax, ay) = (d.x.abs(), d.y.abs());
+    if ax == 0 && ay == 0 { return 0xfa }  // a lone point: ·
+    if ay * 2 <= ax { return 0xc4 }  // ─
+    if ax * 2 <= ay { return 0xb3 }  // │
+    // y points down, so "both positive" is a backslash
+    if (d.x > 0) == (d.y > 0) { b'\\' as u16 } else { b'/' as u16 }
+}
+
+fn ellipse_bounds(center: CellPoint, radii: CellSize) -> CellRect {
+    CellRect::new(
+        center - vec2(radii.width, radii.height),
+        size2(radii.width * 2 + 1, radii.height * 2 + 1),
+    )
+}
+
+fn in_ellipse(d: CellVector, radii: CellSize) -> bool {
+    // the half-cell fudge keeps small circles from looking like diamonds
+    let rx = radii.width as f32 + 0.5;
+    let ry = radii.height as f32 + 0.5;
+    let nx = d.x as f32 / rx;
+    let ny = d.y as f32 / ry;
+    nx * nx + ny * ny <= 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_include_both_ends_and_step_one_cell() {
+        let ends = [(0, 0), (5, 2), (-3, 7), (4, -4), (0, -6), (2, 2)];
+        for (x, y) in ends {
+            let to = point2(x, y);
+            let points = line_points(point2(0, 0), to);
+            assert_eq!(points.first(), Some(&point2(0, 0)));
+            assert_eq!(points.last(), Some(&to));
+            assert_eq!(points.len() as isize, x.abs().max(y.abs()) + 1);
+            for w in points.windows(2) {
+                let d = w[1] - w[0];
+                assert!(d.x.abs() <= 1 && d.y.abs() <= 1 && d != vec2(0, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn lines_are_straight_where_they_can_be() {
+        assert_eq!(line_points(point2(1, 1), point2(4, 1)), vec![point2(1, 1), point2(2, 1), point2(3, 1), point2(4, 1)]);
+        assert_eq!(line_points(point2(0, 0), point2(-2, -2)), vec![point2(0, 0), point2(-1, -1), point2(-2, -2)]);
+        assert_eq!(line_points(point2(0, 0), point2(4, 2)), vec![point2(0, 0), point2(1, 1), point2(2, 1), point2(3, 2), point2(4, 2)]);
+    }
+}
@@ -15,8 +15,8 @@ impl <'a> Brush<'a> {
     pub fn split_horizontally(&self, x: isize) -> (Brush<'a>, Brush<'a>) {
         let x = self.rect.max_x().min(self.rect.min_x().max(x));
 
-        let rect_1 = rect(self.rect.min_x(), self.rect.min_y(), x - self.rect.min_x(), self.rect.max_y());
-        let rect_2 = rect(x, self.rect.min_y(), self.rect.max_x() - x, self.rect.max_y());
+        let rect_1 = rect(self.rect.min_x(), self.rect.min_y(), x - self.rect.min_x(), self.rect.height());
+        let rect_2 = rect(x, self.rect.min_y(), self.rect.max_x() - x, self.rect.height());
 
         return (self.region(rect_1), self.region(rect_2))
     }
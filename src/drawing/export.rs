@@ -0,0 +1,138 @@
+use std::fmt::Write;
+
+use crate::{Screen, aliases::*, cp437, rendering::{DEFAULT_SWATCH, SemanticContent}};
+
+enum Exported {
+    Glyph(char),
+    Continuation,  // covered by a glyph emitted from another cell
+}
+
+type Row = Vec<(char, u8, u8)>;
+
+impl Screen {
+    /// Dump the screen as UTF-8 text, one line per row of glyphs.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for row in self.export_rows() {
+            let line: String = row.iter().map(|(c, _, _)| *c).collect();
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Dump the screen as UTF-8 text with 24-bit ANSI color escapes, in the default colors.
+    /// IO::to_ansi uses the window's colors instead.
+    pub fn to_ansi(&self) -> String {
+        self.to_ansi_with(|c| DEFAULT_SWATCH.get(c))
+    }
+
+    pub(crate) fn to_ansi_with(&self, rgb: impl Fn(u8) -> u32) -> String {
+        let mut out = String::new();
+        for row in self.export_rows() {
+            let mut last: Option<(u8, u8)> = None;
+            for (c, bg, fg) in row {
+                if last != Some((bg, fg)) {
+                    let bg_rgb = rgb(bg);
+                    let fg_rgb = rgb(fg);
+                    write!(
+                        out, "\x1b[48;2;{};{};{}m\x1b[38;2;{};{};{}m",
+                        bg_rgb >> 16 & 0xff, bg_rgb >> 8 & 0xff, bg_rgb & 0xff,
+                        fg_rgb >> 16 & 0xff, fg_rgb >> 8 & 0xff, fg_rgb & 0xff,
+                    ).unwrap();
+                    last = Some((bg, fg));
+                }
+                out.push(c);
+            }
+            out.push_str("\x1b[0m\n");
+        }
+        out
+    }
+
+    fn export_rows(&self) -> Vec<Row> {
+        let mut rows = vec![];
+        let mut blank_run = 0;
+        for y in self.rect().min_y()..self.rect().max_y() {
+            match self.export_row(y) {
+                RowKind::Continuation => {}
+                // Normal text takes two cell rows per line, so empty space does too
+                RowKind::Blank(row) => {
+                    if blank_run % 2 == 0 { rows.push(row) }
+                    blank_run += 1;
+                }
+                RowKind::Text(row) => {
+                    blank_run = 0;
+                    rows.push(row)
+                }
+            }
+        }
+        rows
+    }
+
+    fn export_row(&self, y: isize) -> RowKind {
+        let mut row = vec![];
+        let mut any_continuation = false;
+        let mut any_glyph = false;
+
+        for x in self.rect().min_x()..self.rect().max_x() {
+            let content = self.cells.get(point2(x, y)).unwrap().get();
+            match export_sem(content.sem) {
+                Exported::Glyph(c) => {
+                    if let SemanticContent::Blank = content.sem {} else { any_glyph = true }
+                    row.push((c, content.bg, content.fg))
+                }
+                Exported::Continuation => {
+                    // keep column x at index x
+                    any_continuation = true;
+                    row.push((' ', content.bg, content.fg))
+                }
+            }
+        }
+
+        if any_glyph { RowKind::Text(row) }
+        else if any_continuation { RowKind::Continuation }
+        else { RowKind::Blank(row) }
+    }
+}
+
+enum RowKind {
+    Text(Row),
+    Blank(Row),
+    Continuation,  // only continues glyphs from the row above
+}
+
+fn export_sem(sem: SemanticContent) -> Exported {
+    use SemanticContent::*;
+
+    match sem {
+        Blank => Exported::Glyph(' '),
+        Small(u) | TopHalf(u) | SetTL(u) | FatTL(u) => Exported::Glyph(export_char(u)),
+        // both halves of a pizza can't be shown, so show the first
+        SmallPizza1(u, _) | SmallPizza2(u, _) => Exported::Glyph(export_char(u)),
+        Quadrants(mask) => Exported::Glyph(QUADRANTS[mask as usize & 0xf]),
+        Octants(mask) => Exported::Glyph(braille(mask)),
+        BottomHalf(_) | SetTR(_) | SetBL(_) | SetBR(_) | FatTR(_) | FatBL(_) | FatBR(_) =>
+            Exported::Continuation,
+    }
+}
+
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛',
+    '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+fn braille(mask: u8) -> char {
+    // braille numbers its dots down the left column, then the right, then the bottom row
+    const DOTS: [u8; 8] = [0, 3, 1, 4, 2, 5, 6, 7];
+    let mut code = 0;
+    for (bit, dot) in DOTS.iter().enumerate() {
+        if mask & (1 << bit) != 0 { code |= 1 << dot }
+    }
+    char::from_u32(0x2800 + code).unwrap()
+}
+
+fn export_char(u: u16) -> char {
+    if u > 0xff { return '?' }
+    let c = cp437::decode_char(u as u8);
+    if c.is_control() { ' ' } else { c }
+}
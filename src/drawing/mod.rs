@@ -1,9 +1,14 @@
 mod boxart;
 mod brush;
+mod export;
+mod pixels;
+mod rexpaint;
 mod screen;
 mod stamp;
 
-pub use boxart::BoxArt;
-pub use brush::{Brush, Brushable};
+pub use boxart::{BoxArt, BoxStyle, Frame};
+pub use brush::{Brush, Brushable, Constraint, GradientShape, Layout};
+pub use pixels::{PixelCanvas, SubCell};
 pub use screen::Screen;
+pub(crate) use screen::Snapshot;
 pub use stamp::Stamp;
\ No newline at end of file
@@ -0,0 +1,100 @@
+use euclid::size2;
+use gridd_euclid::{CopyEndlessGrid, PointsIn};
+
+use crate::{Brush, Brushable, FSem, aliases::*, rendering::SemanticContent};
+
+use super::brush::line_points;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubCell {
+    Quadrants,  // 2x2 pixels per cell
+    Octants,  // 2x4 pixels per cell
+}
+
+impl SubCell {
+    pub fn pixels_per_cell(&self) -> CellSize {
+        match self {
+            SubCell::Quadrants => size2(2, 2),
+            SubCell::Octants => size2(2, 4),
+        }
+    }
+}
+
+// Coordinates here are in pixels, not cells.
+// Every cell can only have one fg and bg, so pixels are either on or off.
+pub struct PixelCanvas {
+    pub sub_cell: SubCell,
+    content: CopyEndlessGrid<u8, CellSpace>,  // per cell, bit (row * 2 + col)
+}
+
+impl PixelCanvas {
+    pub fn new(sub_cell: SubCell) -> PixelCanvas {
+        PixelCanvas {
+            sub_cell,
+            content: CopyEndlessGrid::new(0),
+        }
+    }
+
+    pub fn draw(&self, brush: &Brush) {
+        for xy in isize::points_in(self.content.rect()) {
+            let sem = self.cell_sem(self.content.get(xy));
+            brush.draw(xy, FSem::new().sem(sem));
+        }
+    }
+
+    /// The region covered, in cells.
+    pub fn rect(&self) -> CellRect {
+        self.content.rect()
+    }
+
+    pub fn get(&self, at: CellPoint) -> bool {
+        let (cell, bit) = self.locate(at);
+        self.content.get(cell) & bit != 0
+    }
+
+    pub fn set(&mut self, at: CellPoint, on: bool) {
+        let (cell, bit) = self.locate(at);
+        let existing = self.content.get(cell);
+        self.content.set(cell, if on { existing | bit } else { existing & !bit });
+    }
+
+    pub fn line(&mut self, from: CellPoint, to: CellPoint, on: bool) {
+        for xy in line_points(from, to) {
+            self.set(xy, on)
+        }
+    }
+
+    pub fn fill_rect(&mut self, r: CellRect, on: bool) {
+        for xy in isize::points_in(r) {
+            self.set(xy, on)
+        }
+    }
+
+    fn locate(&self, at: CellPoint) -> (CellPoint, u8) {
+        let ppc = self.sub_cell.pixels_per_cell();
+        let cell = point2(at.x.div_euclid(ppc.width), at.y.div_euclid(ppc.height));
+        let col = at.x.rem_euclid(ppc.width);
+        let row = at.y.rem_euclid(ppc.height);
+        (cell, 1 << (row * 2 + col))
+    }
+
+    fn cell_sem(&self, mask: u8) -> SemanticContent {
+        match self.sub_cell {
+            SubCell::Quadrants => match mask & 0xf {
+                // prefer real CP437 blocks where one exists
+                0b0000 => SemanticContent::Blank,
+                0b1111 => SemanticContent::Small(0xdb),
+                0b1100 => SemanticContent::Small(0xdc),
+                0b0101 => SemanticContent::Small(0xdd),
+                0b1010 => SemanticContent::Small(0xde),
+                0b0011 => SemanticContent::Small(0xdf),
+                m => SemanticContent::Quadrants(m),
+            }
+            SubCell::Octants => match mask {
+                0 => SemanticContent::Blank,
+                0xff => SemanticContent::Small(0xdb),
+                m => SemanticContent::Octants(m),
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::{collections::BTreeMap, fs::File, io::{self, Read}, path::Path};
+
+use flate2::read::GzDecoder;
+
+use crate::{Stamp, aliases::*, formatting::FChar, rendering::{DEFAULT_SWATCH, Font}};
+
+// REXPaint marks transparent cells by giving them this bg
+const TRANSPARENT: u32 = 0xff00ff;
+
+struct XpLayer {
+    width: usize,
+    height: usize,
+    cells: Vec<XpCell>,  // column-major, like the file
+}
+
+#[derive(Clone, Copy)]
+struct XpCell {
+    glyph: u32,
+    fg: u32,
+    bg: u32,
+}
+
+impl XpLayer {
+    fn get(&self, x: usize, y: usize) -> Option<XpCell> {
+        let cell = self.cells[x * self.height + y];
+        if cell.bg == TRANSPARENT { return None }
+        Some(cell)
+    }
+}
+
+impl Stamp {
+    /// Load every layer of a REXPaint .xp file, bottom layer first.
+    pub fn load_xp_layers(path: impl AsRef<Path>, font: Font) -> io::Result<Vec<Stamp>> {
+        let mut bytes = vec![];
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Stamp::from_xp_layers(&bytes, font)
+    }
+
+    /// Load a REXPaint .xp file with all its layers flattened into one stamp.
+    pub fn load_xp(path: impl AsRef<Path>, font: Font) -> io::Result<Stamp> {
+        let mut bytes = vec![];
+        File::open(path)?.read_to_end(&mut bytes)?;
+        Stamp::from_xp(&bytes, font)
+    }
+
+    pub fn from_xp_layers(gzipped: &[u8], font: Font) -> io::Result<Vec<Stamp>> {
+        let layers = parse_xp(gzipped)?;
+        let mut colors = BTreeMap::new();
+        Ok(layers.iter().map(|l| {
+            let mut stamp = Stamp::new();
+            draw_layer(&mut stamp, l, font, &mut colors);
+            stamp
+        }).collect())
+    }
+
+    pub fn from_xp(gzipped: &[u8], font: Font) -> io::Result<Stamp> {
+        let layers = parse_xp(gzipped)?;
+        let mut colors = BTreeMap::new();
+        let mut stamp = Stamp::new();
+        for l in layers.iter() {
+            draw_layer(&mut stamp, l, font, &mut colors);
+        }
+        Ok(stamp)
+    }
+}
+
+fn draw_layer(stamp: &mut Stamp, layer: &XpLayer, font: Font, colors: &mut BTreeMap<u32, u8>) {
+    let sz = font.char_size();
+    let mut nearest = |rgb: u32| *colors.entry(rgb).or_insert_with(|| DEFAULT_SWATCH.nearest(rgb));
+
+    for x in 0..layer.width {
+        for y in 0..layer.height {
+            let cell = if let Some(c) = layer.get(x, y) { c } else { continue };
+
+            // glyphs are already CP437 codes
+            let mut fc = FChar::new().sprite(cell.glyph as u16);
+            fc.bg = Some(nearest(cell.bg));
+            fc.fg = Some(nearest(cell.fg));
+
+            font.draw_char(point2(x as isize * sz.width, y as isize * sz.height), fc, stamp);
+        }
+    }
+}
+
+fn parse_xp(gzipped: &[u8]) -> io::Result<Vec<XpLayer>> {
+    let mut bytes = vec![];
+    GzDecoder::new(gzipped).read_to_end(&mut bytes)?;
+    let mut r = XpReader { bytes: &bytes, at: 0 };
+
+    let _version = r.i32()?;
+    let n_layers = r.i32()?;
+    if n_layers < 0 { return Err(invalid("negative layer count")) }
+
+    let mut layers = vec![];
+    for _ in 0..n_layers {
+        let width = r.i32()?;
+        let height = r.i32()?;
+        if width < 0 || height < 0 { return Err(invalid("negative layer size")) }
+        let (width, height) = (width as usize, height as usize);
+
+        // each cell is 10 bytes: don't trust the header to size the allocation
+        let n_cells = width.checked_mul(height)
+            .filter(|n| n.checked_mul(10).is_some_and(|b| b <= r.remaining()))
+            .ok_or_else(|| invalid("truncated file"))?;
+
+        let mut cells = Vec::with_capacity(n_cells);
+        for _ in 0..n_cells {
+            let glyph = r.u32()?;
+            if glyph > 255 { return Err(invalid("glyph out of CP437 range")) }
+            let fg = r.rgb()?;
+            let bg = r.rgb()?;
+            cells.push(XpCell { glyph, fg, bg });
+        }
+        layers.push(XpLayer { width, height, cells });
+    }
+    Ok(layers)
+}
+
+struct XpReader<'a> {
+    bytes: &'a [u8],
+    at: usize,
+}
+
+impl<'a> XpReader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.at
+    }
+
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.at + n > self.bytes.len() { return Err(invalid("truncated file")) }
+        let slice = &self.bytes[self.at..self.at + n];
+        self.at += n;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn i32(&mut self) -> io::Result<i32> {
+        Ok(self.u32()? as i32)
+    }
+
+    fn rgb(&mut self) -> io::Result<u32> {
+        let b = self.take(3)?;
+        Ok((b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32)
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("bad .xp file: {}", msg))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::{Compression, write::GzEncoder};
+
+    use super::*;
+
+    // one layer; cells are (glyph, fg, bg)
+    fn xp(w: i32, h: i32, cells: &[(u32, u32, u32)]) -> Vec<u8> {
+        let mut raw = vec![];
+        raw.extend(&(-1i32).to_le_bytes());
+        raw.extend(&1i32.to_le_bytes());
+        raw.extend(&w.to_le_bytes());
+        raw.extend(&h.to_le_bytes());
+        for (glyph, fg, bg) in cells {
+            raw.extend(&glyph.to_le_bytes());
+            raw.extend(&fg.to_be_bytes()[1..]);
+            raw.extend(&bg.to_be_bytes()[1..]);
+        }
+        let mut enc = GzEncoder::new(vec![], Compression::default());
+        enc.write_all(&raw).unwrap();
+        enc.finish().unwrap()
+    }
+
+    #[test]
+    fn parses_cells_column_major() {
+        let file = xp(2, 1, &[(b'a' as u32, 0xffffff, 0), (b'b' as u32, 0, TRANSPARENT)]);
+        let layers = parse_xp(&file).unwrap();
+        assert_eq!(layers.len(), 1);
+        let l = &layers[0];
+        assert_eq!((l.width, l.height), (2, 1));
+        let a = l.get(0, 0).unwrap();
+        assert_eq!((a.glyph, a.fg, a.bg), (b'a' as u32, 0xffffff, 0));
+        assert!(l.get(1, 0).is_none());
+    }
+
+    #[test]
+    fn rejects_truncated_file() {
+        let file = xp(2, 2, &[(1, 0, 0), (2, 0, 0), (3, 0, 0)]);
+        assert!(parse_xp(&file).is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_header() {
+        let file = xp(i32::MAX, i32::MAX, &[]);
+        assert!(parse_xp(&file).is_err());
+    }
+
+    #[test]
+    fn rejects_glyph_out_of_range() {
+        let file = xp(1, 1, &[(256, 0, 0)]);
+        assert!(parse_xp(&file).is_err());
+    }
+}
@@ -13,11 +13,12 @@ pub struct Screen {
     pub(crate) cells: Grid<Cell<CellContent>, CellSpace>,  // pub(crate) so the renderer can access this directly
     pub(crate) bg: u8,
     pub(crate) fg: u8,
+    animated: Cell<bool>,  // did anything get an animation since the last clear?
 }
 
 impl Screen {
     pub fn new(bg: u8, fg: u8) -> Screen {
-        Screen { bg, fg, cells: Grid::new(
+        Screen { bg, fg, animated: Cell::new(false), cells: Grid::new(
             rect(0, 0, 0, 0), 
             || Cell::new(CellContent {
                 bg, fg, 
@@ -25,6 +26,7 @@ impl Screen {
                 sem: SemanticContent::Blank, 
                 interactor: InteractorFmt::none(),
                 scroll_interactor: Interactor::none(),
+                animation: None,
             })
         )}
     }
@@ -39,9 +41,11 @@ impl Screen {
                 c.sem = SemanticContent::Blank;
                 c.interactor = InteractorFmt::none();
                 c.scroll_interactor = Interactor::none();
+                c.animation = None;
                 c
             });
         }
+        self.animated.set(false);
     }
 
     pub fn resize(&mut self, sz: CellSize) {
@@ -55,6 +59,7 @@ impl Screen {
                 interactor: InteractorFmt::none(),
                 scroll_interactor: Interactor::none(),
                 bevels: Bevels::new(),
+                animation: None,
             })
         )
     }
@@ -62,6 +67,42 @@ impl Screen {
     pub fn rect(&self) -> CellRect {
         self.cells.rect()
     }
+
+    pub(crate) fn is_animated(&self) -> bool {
+        self.animated.get()
+    }
+
+    // a copy of what's on screen, minus the interactors (they belong to a menu that's gone)
+    pub(crate) fn snapshot(&self) -> Snapshot {
+        let rect = self.cells.rect();
+        let cells = isize::points_in(rect).map(|xy| {
+            let mut c = self.cells.get(xy).unwrap().get();
+            c.interactor = InteractorFmt::none();
+            c.scroll_interactor = Interactor::none();
+            c
+        }).collect();
+        Snapshot { rect, cells }
+    }
+
+    // the screen may have been resized since: only the overlap gets restored
+    pub(crate) fn restore(&self, snapshot: &Snapshot) {
+        let overlap = match self.cells.rect().intersection(&snapshot.rect) {
+            Some(o) => o,
+            None => return,
+        };
+        let width = snapshot.rect.width();
+        for xy in isize::points_in(overlap) {
+            let from = xy - snapshot.rect.origin;
+            let c = snapshot.cells[(from.y * width + from.x) as usize];
+            if c.animation.is_some() { self.animated.set(true) }
+            self.cells.get(xy).unwrap().set(c);
+        }
+    }
+}
+
+pub(crate) struct Snapshot {
+    rect: CellRect,
+    cells: Vec<CellContent>,  // row-major
 }
 
 impl Brushable for Screen {
@@ -76,6 +117,33 @@ impl Brushable for Screen {
             if let Some(sprite) = f.sem { c.sem = sprite; }
             if let Some(interactor) = f.interactor { c.interactor = interactor; }
             if let Some(scroll_interactor) = f.scroll_interactor { c.scroll_interactor = scroll_interactor; }
+            // text drawn over a fire tile shouldn't keep flickering
+            if f.animation.is_some() { 
+                c.animation = f.animation; 
+                self.animated.set(true);
+            } else if f.sem.is_some() || f.fg.is_some() || f.bg.is_some() { 
+                c.animation = None; 
+            }
+            c
+        });
+    }
+
+    fn remap(&self, at: CellPoint, map: &dyn Fn(u8) -> u8) {
+        if !self.cells.rect().contains(at) { return; }
+
+        // 255 means "no color" in bevels and interactors, so leave it be
+        let map_set = |c: u8| if c == 255 { c } else { map(c) };
+
+        let cell = self.cells.get(at).unwrap();
+        cell.update(|mut c| {
+            c.bg = map(c.bg);
+            c.fg = map(c.fg);
+            c.bevels.top = map_set(c.bevels.top);
+            c.bevels.left = map_set(c.bevels.left);
+            c.bevels.right = map_set(c.bevels.right);
+            c.bevels.bottom = map_set(c.bevels.bottom);
+            c.interactor.bg = map_set(c.interactor.bg);
+            c.interactor.fg = map_set(c.interactor.fg);
             c
         });
     }
@@ -21,6 +21,19 @@ impl Stamp {
     }
 
     pub fn draw(&self, b: Brush) {  // TODO: Offset?
+        self.draw_with(b, |f| f)
+    }
+
+    // for things drawn over a live menu (like drag ghosts) that mustn't steal the pointer
+    pub(crate) fn draw_without_interactors(&self, b: Brush) {
+        self.draw_with(b.dont_interfere_with_interactor(), |mut f| {
+            f.interactor = None;
+            f.scroll_interactor = None;
+            f
+        })
+    }
+
+    fn draw_with(&self, b: Brush, alter: impl Fn(FSem) -> FSem) {
         let content = self.content.borrow();
         let intersecting_region = b.shifted_clip().intersection(&content.rect());
 
@@ -30,7 +43,7 @@ impl Stamp {
                 // TODO: Compare area to area of me as a whole
                 for xy in isize::points_in(region) {
                     if let Some(sem) = content.get(xy) { 
-                        b.draw(xy, sem); 
+                        b.draw(xy, alter(sem)); 
                     }
                 }
                 /* 
@@ -59,4 +72,18 @@ impl Brushable for Stamp {
         };
         content.set(at, new);
     }
+
+    fn remap(&self, at: CellPoint, map: &dyn Fn(u8) -> u8) {
+        let mut content = self.content.borrow_mut();
+        if let Some(mut f) = content.get(at) {
+            f.bg = f.bg.map(map);
+            f.fg = f.fg.map(map);
+            f.bevels.remap(map);
+            if let Some(i) = &mut f.interactor {
+                if i.bg != 255 { i.bg = map(i.bg) }
+                if i.fg != 255 { i.fg = map(i.fg) }
+            }
+            content.set(at, Some(f));
+        }
+    }
 }
\ No newline at end of file
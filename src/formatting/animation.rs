@@ -0,0 +1,19 @@
+use super::FSem;
+
+// Frames are 'static so cells stay Copy. Declare them in a `static`:
+//   static WATER: [FSem; 2] = [FSem::new().sem(SemanticContent::Small(0xf7)), FSem::new().sem(SemanticContent::Small(b'~' as u16))];
+// Each frame is laid over whatever was drawn in the cell, so a frame that only sets fg
+// pulses the color and leaves the glyph alone. Interactors in frames are ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    pub frames: &'static [FSem],
+    pub period: u16,  // ticks per frame
+}
+
+impl Animation {
+    pub fn frame(&self, tick: u64) -> Option<&'static FSem> {
+        if self.frames.is_empty() { return None }
+        let ix = tick / self.period.max(1) as u64 % self.frames.len() as u64;
+        Some(&self.frames[ix as usize])
+    }
+}
@@ -1,3 +1,4 @@
+use super::Animation;
 use crate::rendering::{Bevels, Interactor, InteractorFmt, SemanticContent};
 
 pub struct FString(pub Vec<FChar>);
@@ -33,6 +34,7 @@ impl FChar {
             interactor: self.interactor,
             scroll_interactor: self.scroll_interactor,
             bevels: self.bevels,
+            animation: None,
         }
     }
 
@@ -50,10 +52,12 @@ pub struct FSem {
     pub interactor: Option<InteractorFmt>, // if None, then don't _change_ the interactor
     pub scroll_interactor: Option<Interactor>, // if None, then don't _change_ the interactor
     pub bevels: FBevels,
+    pub animation: Option<Animation>, // if None, drawing over the cell stops its animation
 }
 
 impl FSem {
-    pub fn new() -> FSem {
+    // const so animation frames can live in a static
+    pub const fn new() -> FSem {
         FSem {
             sem: None,
             bg: None,
@@ -61,9 +65,17 @@ impl FSem {
             interactor: None,
             scroll_interactor: None,
             bevels: FBevels::new(),
+            animation: None,
         }
     }
 
+    /// Cycle through `frames`, `period` ticks apiece. Runs without calling on_redraw again.
+    pub const fn animated(frames: &'static [FSem], period: u16) -> FSem {
+        let mut f = FSem::new();
+        f.animation = Some(Animation { frames, period });
+        f
+    }
+
     pub fn superimposed_on(self, below: FSem) -> FSem {
         FSem { 
             sem: self.sem.or(below.sem), 
@@ -71,26 +83,31 @@ impl FSem {
             fg: self.fg.or(below.fg),
             interactor: self.interactor.or(below.interactor),
             scroll_interactor: self.scroll_interactor.or(below.scroll_interactor),
-            bevels: self.bevels.superimposed_on(below.bevels)
+            bevels: self.bevels.superimposed_on(below.bevels),
+            animation: if self.animation.is_some() || self.sem.is_some() || self.fg.is_some() || self.bg.is_some() { 
+                self.animation 
+            } else { 
+                below.animation 
+            },
         }
     }
 
-    pub fn sem(mut self, sem: SemanticContent) -> FSem {
+    pub const fn sem(mut self, sem: SemanticContent) -> FSem {
         self.sem = Some(sem);
         self
     }
 
-    pub fn bg(mut self, bg: u8) -> FSem {
+    pub const fn bg(mut self, bg: u8) -> FSem {
         self.bg = Some(bg);
         self
     }
 
-    pub fn fg(mut self, fg: u8) -> FSem {
+    pub const fn fg(mut self, fg: u8) -> FSem {
         self.fg = Some(fg);
         self
     }
 
-    pub fn color(mut self, (bg, fg): (u8, u8)) -> FSem {
+    pub const fn color(mut self, (bg, fg): (u8, u8)) -> FSem {
         self.bg = Some(bg);
         self.fg = Some(fg);
         self
@@ -116,7 +133,7 @@ pub struct FBevels {
     pub bottom: Option<u8>,
 }
 impl FBevels {
-    pub(crate) fn new() -> FBevels {
+    pub(crate) const fn new() -> FBevels {
         FBevels { top: None, left: None, right: None, bottom: None }
     }
 
@@ -128,6 +145,13 @@ impl FBevels {
         bevels
     }
 
+    pub(crate) fn remap(&mut self, map: &dyn Fn(u8) -> u8) {
+        self.top = self.top.map(map);
+        self.left = self.left.map(map);
+        self.right = self.right.map(map);
+        self.bottom = self.bottom.map(map);
+    }
+
     pub(crate) fn update(&self, bevels: &mut Bevels) {
         if let Some(t) = self.top { bevels.top = t }
         if let Some(l) = self.left { bevels.left = l }
@@ -1,5 +1,7 @@
+mod animation;
 mod fstring;
 mod preformatter;
 
+pub use animation::Animation;
 pub use fstring::{FString, FChar, FSem};
 pub use preformatter::{Preformatter, Justification};
\ No newline at end of file
@@ -14,19 +14,36 @@ mod rendering;
 mod window_management;
 
 pub use aliases::{CellSpace, CellPoint, CellVector, CellSize, CellRect};
-pub use drawing::{BoxArt, Brush, Brushable, Screen, Stamp};
-pub use formatting::{FSem, Justification};
-pub use rendering::{colors, Font, Interactor, SemanticContent};
+pub use drawing::{
+    BoxArt, BoxStyle, Brush, Brushable, Constraint, Frame, GradientShape, Layout,
+    PixelCanvas, Screen, Stamp, SubCell,
+};
+pub use formatting::{Animation, FSem, Justification};
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub use rendering::bench as __bench;
+pub use rendering::{colors, ColorMap, CrtConfig, Font, Interactor, SemanticContent};
 pub use window_management::{
     AspectConfig,
+    AsyncIO,
+    Easing,
     InputEvent, 
     Menu,
+    MenuRun,
+    Modal,
+    Modifiers,
     MouseEvent, 
     MouseButton, 
     OnKey,
+    KeyCombo,
+    Keybindings,
     KeyEvent, 
     Keycode,
     KeyRecognizer,
+    NextEvent,
     IO,
+    Resolve,
     Signal,
+    Sleep,
+    Tween,
 };
\ No newline at end of file
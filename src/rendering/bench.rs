@@ -0,0 +1,59 @@
+// Only built with the `bench` feature, so benches/render.rs can get at the rasterizer.
+use euclid::size2;
+use gridd_euclid::PointsIn;
+
+use crate::{Screen, aliases::*, constants::{CELL_X, CELL_Y}, window_management::Aspect};
+
+use super::{ColorMap, DEFAULT_SWATCH, Interactor, Render, rasterize, renderer::RenderContent};
+
+pub struct BenchFrame {
+    width: u16,
+    contents: Vec<RenderContent>,
+    buffer: Vec<u32>,
+}
+
+impl BenchFrame {
+    /// A `width` by `height` terminal full of text, with a few bevels.
+    pub fn new(width: u16, height: u16) -> BenchFrame {
+        let mut screen = Screen::new(DEFAULT_SWATCH.default_bg, DEFAULT_SWATCH.default_fg);
+        screen.resize(size2(width as isize, height as isize));
+        let brush = screen.brush();
+        for y in 0..height as isize / 2 {
+            brush.at(point2(0, y * 2)).fg((y % 64) as u8).putfs("The quick brown bat jumps over the lazy moth. ".repeat(width as usize / 40 + 1).as_str());
+        }
+        brush.region(rect(2, 2, (width as isize / 2).max(0), (height as isize / 2).max(0))).bevel_w95((7, 0));
+
+        let render = Render {
+            aspect: Aspect {
+                buf_size: size2(width * CELL_X as u16, height * CELL_Y as u16),
+                term_size: size2(width, height),
+            },
+            swatch: *DEFAULT_SWATCH,
+            color_map: ColorMap::identity(),
+            interactor: Interactor::none(),
+            tick: 0,
+        };
+        let contents = u16::points_in(render.aspect.term_rect())
+            .map(|xy| render.get_content(&screen, xy.cast()))
+            .collect::<Vec<_>>();
+        let buffer = vec![0; contents.len() * CELL_X * CELL_Y];
+        BenchFrame { width, contents, buffer }
+    }
+
+    /// The old cell-at-a-time path.
+    pub fn legacy(&mut self) {
+        for (i, c) in self.contents.iter().enumerate() {
+            let x = (i % self.width as usize) as u16;
+            let y = (i / self.width as usize) as u16;
+            c.physically_draw(&mut self.buffer, x, y, self.width);
+        }
+    }
+
+    pub fn rows(&mut self, threads: usize) {
+        rasterize(&self.contents, self.width as usize, &mut self.buffer, threads);
+    }
+
+    pub fn buffer(&self) -> &[u32] {
+        &self.buffer
+    }
+}
@@ -1,3 +1,5 @@
+use crate::formatting::Animation;
+
 #[derive(Clone, Copy, Debug)]
 pub struct CellContent {
     pub bg: u8,
@@ -9,6 +11,21 @@ pub struct CellContent {
     pub sem: SemanticContent,
     pub interactor: InteractorFmt, // interactor, mouseover color
     pub scroll_interactor: Interactor,
+
+    pub animation: Option<Animation>,
+}
+
+impl CellContent {
+    // what the cell looks like `tick` ticks in
+    pub fn at_tick(mut self, tick: u64) -> CellContent {
+        if let Some(frame) = self.animation.and_then(|a| a.frame(tick)) {
+            if let Some(bg) = frame.bg { self.bg = bg; }
+            if let Some(fg) = frame.fg { self.fg = fg; }
+            frame.bevels.update(&mut self.bevels);
+            if let Some(sprite) = frame.sem { self.sem = sprite; }
+        }
+        self
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -29,6 +46,10 @@ pub enum SemanticContent {
     FatTR(u16),
     FatBL(u16),
     FatBR(u16),
+
+    // synthesized sub-cell pixels: bit (row * 2 + col) is set for each lit pixel
+    Quadrants(u8),  // 2x2
+    Octants(u8),  // 2x4
     // TODO: Double-wides
 }
 
@@ -0,0 +1,68 @@
+use super::{DEFAULT_SWATCH, colors};
+
+// These are palette-to-palette tables, so they work the same way on
+// already-drawn cells (Brush::apply_color_map) and on the whole screen at render
+// time (IO::set_color_map)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ColorMap(pub [u8; 0x100]);
+
+impl ColorMap {
+    pub fn identity() -> ColorMap {
+        let mut table = [0; 0x100];
+        for (i, t) in table.iter_mut().enumerate() { *t = i as u8 }
+        ColorMap(table)
+    }
+
+    pub fn from_fn(f: impl Fn(u8) -> u8) -> ColorMap {
+        let mut table = [0; 0x100];
+        for (i, t) in table.iter_mut().enumerate() { *t = f(i as u8) }
+        ColorMap(table)
+    }
+
+    pub fn get(&self, color: u8) -> u8 {
+        self.0[color as usize]
+    }
+
+    /// Apply `self`, then `other`.
+    pub fn then(&self, other: &ColorMap) -> ColorMap {
+        ColorMap::from_fn(|c| other.get(self.get(c)))
+    }
+
+    pub fn grayscale() -> ColorMap {
+        ColorMap::onto_ramp(&[colors::Dark, colors::Light].concat())
+    }
+
+    pub fn sepia() -> ColorMap {
+        ColorMap::onto_ramp(&[colors::DkYellow, colors::LtYellow].concat())
+    }
+
+    /// The red "you got hit" look.
+    pub fn damage_flash() -> ColorMap {
+        ColorMap::onto_ramp(&[colors::DkRed, colors::LtRed].concat())
+    }
+
+    /// Flip every color to the other end of its ramp.
+    pub fn invert() -> ColorMap {
+        ColorMap::from_fn(|c| colors::lighten(colors::darken(c, 7), 7 - ramp_step(c)))
+    }
+
+    /// Send every color to the entry in `ramp` with the closest brightness.
+    pub fn onto_ramp(ramp: &[u8]) -> ColorMap {
+        if ramp.is_empty() { return ColorMap::identity() }
+
+        ColorMap::from_fn(|c| {
+            let l = luma(c);
+            *ramp.iter().min_by_key(|r| (luma(**r) - l).abs()).unwrap()
+        })
+    }
+}
+
+fn ramp_step(color: u8) -> u8 {
+    color - colors::darken(color, 7)
+}
+
+fn luma(color: u8) -> i32 {
+    let rgb = DEFAULT_SWATCH.get(color);
+    let (r, g, b) = ((rgb >> 16 & 0xff) as i32, (rgb >> 8 & 0xff) as i32, (rgb & 0xff) as i32);
+    (r * 299 + g * 587 + b * 114) / 1000
+}
@@ -26,4 +26,21 @@ pub const DkPurple: [u8; 4] = [0o70, 0o71, 0o72, 0o73];
 pub const LtPurple: [u8; 4] = [0o74, 0o75, 0o76, 0o77];
 
 pub const DkFuchsia: [u8; 4] = [0o100, 0o101, 0o102, 0o103];
-pub const LtFuchsia: [u8; 4] = [0o104, 0o105, 0o106, 0o107];
\ No newline at end of file
+pub const LtFuchsia: [u8; 4] = [0o104, 0o105, 0o106, 0o107];
+
+// The palette is nine hues (grays first), each an eight-step ramp
+// from Dk[0] up to Lt[3]. Everything past the last hue is unused.
+const N_RAMPS: u8 = 9;
+const RAMP_LEN: u8 = 8;
+
+pub fn darken(color: u8, steps: u8) -> u8 {
+    if color >= N_RAMPS * RAMP_LEN { return color }
+    let base = color - color % RAMP_LEN;
+    base + (color % RAMP_LEN).saturating_sub(steps)
+}
+
+pub fn lighten(color: u8, steps: u8) -> u8 {
+    if color >= N_RAMPS * RAMP_LEN { return color }
+    let base = color - color % RAMP_LEN;
+    base + (color % RAMP_LEN).saturating_add(steps).min(RAMP_LEN - 1)
+}
@@ -0,0 +1,125 @@
+// This runs on the unscaled buffer, so one cell is 8x8 pixels here.
+// Every effect is a strength from 0.0 (off) to about 1.0 (way too much).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CrtConfig {
+    pub scanlines: f32,
+    pub bloom: f32,
+    pub color_bleed: f32,
+    pub curvature: f32,
+}
+
+impl CrtConfig {
+    pub fn subtle() -> CrtConfig {
+        CrtConfig {
+            scanlines: 0.25,
+            bloom: 0.2,
+            color_bleed: 0.3,
+            curvature: 0.04,
+        }
+    }
+}
+
+// the buffers live across frames so we don't reallocate every time
+pub(crate) struct CrtBuffers {
+    pub out: Vec<u32>,
+    scratch: Vec<u32>,
+}
+
+impl CrtBuffers {
+    pub fn new() -> CrtBuffers {
+        CrtBuffers { out: vec![], scratch: vec![] }
+    }
+
+    pub fn apply(&mut self, config: &CrtConfig, src: &[u32], width: usize, height: usize) {
+        self.out.resize(src.len(), 0);
+        self.scratch.resize(src.len(), 0);
+        if width == 0 || height == 0 { return }
+
+        // pass 1: curvature and color bleed, sampling from src
+        let cx = (width as f32 - 1.0) / 2.0;
+        let cy = (height as f32 - 1.0) / 2.0;
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = if config.curvature > 0.0 {
+                    let u = (x as f32 - cx) / cx.max(1.0);
+                    let v = (y as f32 - cy) / cy.max(1.0);
+                    let warp = 1.0 + config.curvature * (u * u + v * v);
+                    let sx = (u * warp * cx + cx).round();
+                    let sy = (v * warp * cy + cy).round();
+                    if sx < 0.0 || sy < 0.0 || sx >= width as f32 || sy >= height as f32 {
+                        self.out[y * width + x] = 0;
+                        continue;
+                    }
+                    (sx as usize, sy as usize)
+                } else { (x, y) };
+
+                let here = src[sy * width + sx];
+                self.out[y * width + x] = if config.color_bleed > 0.0 {
+                    // red smears right, blue smears left
+                    let left = src[sy * width + sx.saturating_sub(1)];
+                    let right = src[sy * width + (sx + 1).min(width - 1)];
+                    let r = mix(channel(here, 16), channel(left, 16), config.color_bleed * 0.5);
+                    let b = mix(channel(here, 0), channel(right, 0), config.color_bleed * 0.5);
+                    rgb(r, channel(here, 8), b)
+                } else { here };
+            }
+        }
+
+        // pass 2: blur horizontally into scratch, for bloom
+        if config.bloom > 0.0 {
+            for y in 0..height {
+                for x in 0..width {
+                    let row = y * width;
+                    let a = self.out[row + x.saturating_sub(1)];
+                    let b = self.out[row + x];
+                    let c = self.out[row + (x + 1).min(width - 1)];
+                    self.scratch[row + x] = average3(a, b, c);
+                }
+            }
+        }
+
+        // pass 3: finish the blur vertically, add it back, then darken the scanlines
+        for y in 0..height {
+            let scan = if y % 2 == 1 { 1.0 - config.scanlines.clamp(0.0, 1.0) } else { 1.0 };
+            for x in 0..width {
+                let i = y * width + x;
+                let mut px = self.out[i];
+
+                if config.bloom > 0.0 {
+                    let a = self.scratch[y.saturating_sub(1) * width + x];
+                    let b = self.scratch[i];
+                    let c = self.scratch[(y + 1).min(height - 1) * width + x];
+                    let glow = average3(a, b, c);
+                    px = rgb(
+                        channel(px, 16) + channel(glow, 16) * config.bloom,
+                        channel(px, 8) + channel(glow, 8) * config.bloom,
+                        channel(px, 0) + channel(glow, 0) * config.bloom,
+                    );
+                }
+
+                if scan < 1.0 {
+                    px = rgb(channel(px, 16) * scan, channel(px, 8) * scan, channel(px, 0) * scan);
+                }
+                self.out[i] = px;
+            }
+        }
+    }
+}
+
+fn channel(px: u32, shift: u32) -> f32 {
+    (px >> shift & 0xff) as f32
+}
+
+fn rgb(r: f32, g: f32, b: f32) -> u32 {
+    let c = |v: f32| v.clamp(0.0, 255.0) as u32;
+    c(r) << 16 | c(g) << 8 | c(b)
+}
+
+fn mix(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn average3(a: u32, b: u32, c: u32) -> u32 {
+    let avg = |shift| (channel(a, shift) + channel(b, shift) + channel(c, shift)) / 3.0;
+    rgb(avg(16), avg(8), avg(0))
+}
@@ -84,6 +84,9 @@ pub fn eval(content: SemanticContent) -> Tile {
         SemanticContent::FatBL(u) => { FONT_FAT.tile((u as usize) * 4 + 2) }
         SemanticContent::FatBR(u) => { FONT_FAT.tile((u as usize) * 4 + 3) }
 
+        SemanticContent::Quadrants(mask) => { subcell_tile(mask, 2) }
+        SemanticContent::Octants(mask) => { subcell_tile(mask, 4) }
+
         SemanticContent::SmallPizza1(u1, u2) => {
             Tile(
                 from_u64(
@@ -103,6 +106,15 @@ pub fn eval(content: SemanticContent) -> Tile {
     }
 }
 
+fn subcell_tile(mask: u8, n_rows: usize) -> Tile {
+    let mut rows = [0; 8];
+    for (y, row) in rows.iter_mut().enumerate() {
+        let bits = mask >> (y * n_rows / 8 * 2) & 0b11;
+        *row = if bits & 1 != 0 { 0x0f } else { 0 } | if bits & 2 != 0 { 0xf0 } else { 0 };
+    }
+    Tile(rows)
+}
+
 const fn to_u64(u8s: [u8; 8]) -> u64 {
     u64::from_le_bytes(u8s)
 }
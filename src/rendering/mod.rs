@@ -1,4 +1,9 @@
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
 mod cell;
+mod color_map;
+mod crt;
 pub(crate) mod font;
 mod renderer;
 mod sprite;
@@ -8,6 +13,9 @@ mod swatch;
 
 pub(crate) use cell::{Bevels, CellContent, InteractorFmt};
 pub use cell::{Interactor, SemanticContent};
+pub use color_map::ColorMap;
+pub use crt::CrtConfig;
+pub(crate) use crt::CrtBuffers;
 pub use font::Font;
-pub(crate) use renderer::Render;
+pub(crate) use renderer::{Render, rasterize};
 pub(crate) use swatch::{DEFAULT_SWATCH, Swatch};
\ No newline at end of file
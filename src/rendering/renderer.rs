@@ -2,19 +2,21 @@ use crate::{aliases::*, constants::{CELL_X, CELL_Y}, drawing::Screen};
 
 use crate::window_management::Aspect;
 
-use super::{Interactor, Swatch, sprite::Tile};
+use super::{ColorMap, Interactor, Swatch, sprite::Tile};
 
 #[derive(Eq, PartialEq)]
 pub(crate) struct Render {
     pub aspect: Aspect,
     pub swatch: Swatch,
+    pub color_map: ColorMap,
     pub interactor: Interactor,
+    pub tick: u64,  // for animated cells
 }
 
 
 impl Render {
     pub fn get_content(&self, screen: &Screen, term_xy: CellPoint) -> RenderContent {
-        let content = screen.cells.get(term_xy).unwrap().get();
+        let content = screen.cells.get(term_xy).unwrap().get().at_tick(self.tick);
         let interacting_here = if self.interactor == Interactor::none() { 
             false 
         } else { 
@@ -32,20 +34,22 @@ impl Render {
             bg = content.bg;
         };
 
+        let color = |c: u8| self.swatch.get(self.color_map.get(c));
+
         RenderContent {
-            tile, bg: self.swatch.get(bg), fg: self.swatch.get(fg),
+            tile, bg: color(bg), fg: color(fg),
 
             bevel_top: !(interacting_here || content.bevels.top == 255), 
-            bevel_top_fg: self.swatch.get(content.bevels.top),
+            bevel_top_fg: color(content.bevels.top),
 
             bevel_left: !(interacting_here || content.bevels.left == 255), 
-            bevel_left_fg: self.swatch.get(content.bevels.left),
+            bevel_left_fg: color(content.bevels.left),
 
             bevel_right: !(interacting_here || content.bevels.right == 255), 
-            bevel_right_fg: self.swatch.get(content.bevels.right),
+            bevel_right_fg: color(content.bevels.right),
 
             bevel_bottom: !(interacting_here || content.bevels.bottom == 255), 
-            bevel_bottom_fg: self.swatch.get(content.bevels.bottom),
+            bevel_bottom_fg: color(content.bevels.bottom),
         }
     }
 }
@@ -60,10 +64,110 @@ pub struct RenderContent {
     bevel_bottom: bool, bevel_bottom_fg: u32,
 }
 
+lazy_static! {
+    // for each row of a tile: !0 where the glyph is lit, 0 where it isn't
+    static ref ROW_MASKS: [[u32; CELL_X]; 256] = {
+        let mut masks = [[0; CELL_X]; 256];
+        for (bits, mask) in masks.iter_mut().enumerate() {
+            for (x, m) in mask.iter_mut().enumerate() {
+                if bits >> x & 1 == 1 { *m = !0 }
+            }
+        }
+        masks
+    };
+}
+
+const MIN_ROWS_PER_THREAD: usize = 32;
+
+/// Rasterize a whole screen's worth of content, one row of cells at a time.
+/// `contents` is row-major, `term_width` cells across.
+pub(crate) fn rasterize(contents: &[RenderContent], term_width: usize, buffer: &mut [u32], threads: usize) {
+    let cell_row_len = term_width * CELL_X * CELL_Y;
+    if cell_row_len == 0 { return }
+    assert_eq!(contents.len() * CELL_X * CELL_Y, buffer.len());
+
+    // spawning threads costs about as much as drawing a few dozen rows, so only split big screens
+    let n_cell_rows = contents.len() / term_width;
+    let threads = threads.max(1).min((n_cell_rows / MIN_ROWS_PER_THREAD).max(1));
+    if threads == 1 {
+        for (out, row) in buffer.chunks_mut(cell_row_len).zip(contents.chunks(term_width)) {
+            rasterize_cell_row(row, out);
+        }
+        return
+    }
+
+    // split by whole cell rows so no two threads ever share a pixel row
+    let rows_per_thread = n_cell_rows.div_ceil(threads);
+    std::thread::scope(|scope| {
+        let draw = move |out: &mut [u32], these: &[RenderContent]| {
+            for (out, row) in out.chunks_mut(cell_row_len).zip(these.chunks(term_width)) {
+                rasterize_cell_row(row, out);
+            }
+        };
+        let mut jobs = buffer.chunks_mut(rows_per_thread * cell_row_len)
+            .zip(contents.chunks(rows_per_thread * term_width));
+        // this thread takes the first chunk instead of sitting idle
+        let first = jobs.next();
+        for (out, these) in jobs {
+            scope.spawn(move || draw(out, these));
+        }
+        if let Some((out, these)) = first { draw(out, these) }
+    });
+}
+
+fn rasterize_cell_row(row: &[RenderContent], out: &mut [u32]) {
+    let line_len = row.len() * CELL_X;
+    for (y, line) in out.chunks_exact_mut(line_len).enumerate() {
+        for (px, content) in line.chunks_exact_mut(CELL_X).zip(row.iter()) {
+            content.pixel_row(y, px);
+        }
+    }
+}
+
 impl RenderContent {
+    // one row of pixels, left to right. `out` is exactly CELL_X long
+    fn pixel_row(&self, y: usize, out: &mut [u32]) {
+        let mask = &ROW_MASKS[self.tile.0[y] as usize];
+        for (o, m) in out.iter_mut().zip(mask.iter()) {
+            *o = (self.fg & m) | (self.bg & !m);
+        }
+
+        if !(self.bevel_top || self.bevel_left || self.bevel_right || self.bevel_bottom) {
+            return
+        }
+
+        // bevels go under the glyph. top and bottom supercede left and right
+        let edge = if self.bevel_top && y == 0 {
+            Some(self.bevel_top_fg)
+        } else if self.bevel_bottom && y == CELL_Y - 1 {
+            Some(self.bevel_bottom_fg)
+        } else { None };
+
+        for (x, (o, m)) in out.iter_mut().zip(mask.iter()).enumerate() {
+            if *m != 0 { continue }
+            if let Some(e) = edge { *o = e }
+            else if self.bevel_left && x == 0 { *o = self.bevel_left_fg }
+            else if self.bevel_right && x == CELL_X - 1 { *o = self.bevel_right_fg }
+        }
+    }
+
+    // draw just this cell, a row at a time
+    pub(crate) fn draw_cell(&self, out_buf: &mut [u32], out_x: u16, out_y: u16, out_width: u16) {
+        let real_out_x = out_x as usize * CELL_X;
+        let real_out_y = out_y as usize * CELL_Y;
+        let real_out_width = out_width as usize * CELL_X;
+
+        for y in 0..CELL_Y {
+            let start = (real_out_y + y) * real_out_width + real_out_x;
+            self.pixel_row(y, &mut out_buf[start..start + CELL_X]);
+        }
+    }
+
+    // the old pixel-at-a-time path, kept to benchmark against
+    #[cfg(feature = "bench")]
     pub(crate) fn physically_draw(
         &self, 
-        out_buf: &mut Vec<u32>,  // TODO: Do this unchecked
+        out_buf: &mut [u32],  // TODO: Do this unchecked
         out_x: u16, out_y: u16, out_width: u16, 
     ) {
         if !(self.bevel_top || self.bevel_left || self.bevel_right || self.bevel_bottom) {
@@ -128,9 +232,10 @@ impl RenderContent {
         }
     }
 
+    #[cfg(feature = "bench")]
     pub(crate) fn render_fast(
         &self, 
-        out_buf: &mut Vec<u32>, 
+        out_buf: &mut [u32], 
         out_x: u16, out_y: u16, out_width: u16, 
     ) {
         let real_out_x = out_x as usize * CELL_X;
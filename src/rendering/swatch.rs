@@ -1,5 +1,8 @@
 use std::convert::TryInto;
 
+// nine ramps of eight. everything after that is filler
+const N_REAL_COLORS: usize = 72;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Swatch {
     pub colors: [u32; 0x100],
@@ -11,6 +14,24 @@ impl Swatch {
     pub fn get(&self, color: u8) -> u32 {
         self.colors[color as usize]
     }
+
+    // nearest color by plain RGB distance -- good enough for imported art.
+    // only real palette colors, never the filler entries
+    pub fn nearest(&self, rgb: u32) -> u8 {
+        fn channels(c: u32) -> (i32, i32, i32) {
+            ((c >> 16 & 0xff) as i32, (c >> 8 & 0xff) as i32, (c & 0xff) as i32)
+        }
+
+        let (r, g, b) = channels(rgb);
+        let mut best = (0, i32::MAX);
+        for (i, c) in self.colors[..N_REAL_COLORS].iter().enumerate() {
+            let (r2, g2, b2) = channels(*c);
+            let dist = (r - r2) * (r - r2) + (g - g2) * (g - g2) + (b - b2) * (b - b2);
+            if dist < best.1 { best = (i as u8, dist) }
+            if dist == 0 { break }
+        }
+        best.0
+    }
 }
 
 const SWATCH_DATA: &[u8; 0x300] = include_bytes!("swatch.bin");
@@ -31,4 +52,15 @@ lazy_static! {
             default_fg: 7,
         }
     };
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_skips_filler() {
+        let filler = DEFAULT_SWATCH.get(0xff);
+        assert!((DEFAULT_SWATCH.nearest(filler) as usize) < N_REAL_COLORS);
+        assert_eq!(DEFAULT_SWATCH.get(DEFAULT_SWATCH.nearest(DEFAULT_SWATCH.get(12))), DEFAULT_SWATCH.get(12));
+    }
+}
@@ -0,0 +1,239 @@
+use std::{cell::{Cell, RefCell}, future::Future, pin::Pin, ptr, rc::Rc, task::{Context, Poll, RawWaker, RawWakerVTable, Waker}, time::{Duration, Instant}};
+
+use crate::{IO, Menu, Screen, Signal};
+
+use super::{DEFAULT_TICKS_PER_SECOND, EventLoop, InputEvent, Resume};
+
+// The executor is IO::block_on. It polls the one future it was given, and
+// whenever that's pending it pumps the window until one event comes in, then polls again.
+// Nothing here ever wakes up on its own, so the waker doesn't do anything.
+//
+// Only one future gets each event: if you join two things that both want input,
+// whichever is polled first wins.
+pub struct AsyncIO<'a> {
+    state: Rc<AsyncState<'a>>,
+}
+
+type Scene<'a> = Box<dyn 'a+FnMut(&Screen)>;
+type Modal = Box<dyn FnOnce(&mut IO) -> Signal>;
+
+struct AsyncState<'a> {
+    scene: RefCell<Scene<'a>>,
+    scene_id: Cell<u64>,  // bumped by every draw(), so a menu knows if the scene is still its own
+    event: Cell<Option<InputEvent>>,
+    refresh: Cell<bool>,
+    ticks_per_second: Cell<u32>,
+
+    // Signal::Modal needs &mut IO, which only the executor has
+    modal: RefCell<Option<Modal>>,
+    modal_result: RefCell<Option<Signal>>,
+}
+
+impl<'a> AsyncIO<'a> {
+    fn new() -> AsyncIO<'a> {
+        AsyncIO { state: Rc::new(AsyncState {
+            scene: RefCell::new(Box::new(|_| {})),
+            scene_id: Cell::new(0),
+            event: Cell::new(None),
+            refresh: Cell::new(true),
+            ticks_per_second: Cell::new(DEFAULT_TICKS_PER_SECOND),
+            modal: RefCell::new(None),
+            modal_result: RefCell::new(None),
+        })}
+    }
+
+    pub fn share(&self) -> AsyncIO<'a> {
+        AsyncIO { state: self.state.clone() }
+    }
+
+    /// What to draw while waiting on next_event() or sleep(). Blank until you call this.
+    pub fn draw(&self, on_redraw: impl 'a+FnMut(&Screen)) {
+        *self.state.scene.borrow_mut() = Box::new(on_redraw);
+        self.state.scene_id.set(self.state.scene_id.get() + 1);
+        self.refresh();
+    }
+
+    pub fn refresh(&self) {
+        self.state.refresh.set(true);
+    }
+
+    pub fn next_event(&self) -> NextEvent<'a> {
+        NextEvent { io: self.share() }
+    }
+
+    /// Events that come in while asleep are thrown away, like IO::sleep.
+    pub fn sleep(&self, time: Duration) -> Sleep<'a> {
+        Sleep { io: self.share(), until: Instant::now() + time }
+    }
+
+    /// Like IO::menu. Finishes when a handler returns Signal::Break, and takes the menu 
+    /// off the screen when it does.
+    pub fn menu(&self, mut on_redraw: impl 'a+FnMut(&Screen, Menu<'a>)) -> MenuRun<'a> {
+        let menu = Menu::new();
+        let shared = menu.share();
+        self.draw(move |out| {
+            shared.clear();
+            on_redraw(out, shared.share());
+            shared.draw_tooltip(out);
+            shared.draw_drag_ghost(out);
+        });
+        MenuRun { io: self.share(), menu, scene_id: self.state.scene_id.get() }
+    }
+}
+
+pub struct NextEvent<'a> { io: AsyncIO<'a> }
+
+impl<'a> Future for NextEvent<'a> {
+    type Output = InputEvent;
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<InputEvent> {
+        match self.io.state.event.take() {
+            Some(e) => Poll::Ready(e),
+            None => Poll::Pending,
+        }
+    }
+}
+
+pub struct Sleep<'a> { io: AsyncIO<'a>, until: Instant }
+
+impl<'a> Future for Sleep<'a> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        self.io.state.event.take();
+        if Instant::now() >= self.until { Poll::Ready(()) } else { Poll::Pending }
+    }
+}
+
+pub struct MenuRun<'a> { io: AsyncIO<'a>, menu: Menu<'a>, scene_id: u64 }
+
+impl<'a> MenuRun<'a> {
+    // a finished menu shouldn't keep drawing, or keep its handlers
+    fn finish(&self) {
+        self.menu.clear();
+        if self.io.state.scene_id.get() == self.scene_id {
+            self.io.draw(|_| {});
+        }
+    }
+}
+
+impl<'a> Drop for MenuRun<'a> {
+    fn drop(&mut self) { self.finish() }
+}
+
+impl<'a> Future for MenuRun<'a> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        let state = &self.io.state;
+        self.menu.set_tick_rate(state.ticks_per_second.get());
+        let signal = match state.modal_result.take() {
+            Some(s) => s,
+            None => match state.event.take().and_then(|e| self.menu.handle(e)) {
+                Some(s) => s,
+                None => return Poll::Pending,
+            }
+        };
+
+        match signal {
+            Signal::Break => { self.finish(); Poll::Ready(()) }
+            Signal::Modal(m) => { state.modal.replace(Some(m)); Poll::Pending }
+            Signal::Continue => Poll::Pending,
+            Signal::Refresh => { state.refresh.set(true); Poll::Pending }
+        }
+    }
+}
+
+impl IO {
+    /// Run `script` to completion, pumping the window whenever it waits on something.
+    pub fn block_on<'a, T, F: Future<Output = T>>(&mut self, script: impl FnOnce(AsyncIO<'a>) -> F) -> T {
+        let io = AsyncIO::new();
+        let mut future = Box::pin(script(io.share()));
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            io.state.ticks_per_second.set(self.ticks_per_second);
+            if let Poll::Ready(t) = future.as_mut().poll(&mut cx) {
+                self.must_refresh = true;
+                return t
+            }
+
+            let state = &io.state;
+            if state.refresh.take() { self.must_refresh = true; }
+
+            let modal = state.modal.borrow_mut().take();
+            if let Some(m) = modal {
+                self.must_refresh = true;
+                let result = m(self);
+                state.modal_result.replace(Some(result));
+                self.must_refresh = true;
+                continue
+            }
+
+            let mut event = None;
+            self.wait(EventLoop {
+                on_redraw: Box::new(|io| (state.scene.borrow_mut())(io.screen.target())),
+                on_exit: Box::new(self.default_on_exit),
+
+                on_input: Box::new(|_, i| {
+                    event = Some(i);
+                    Resume::PopEvtLoop
+                }),
+            });
+            state.event.set(event);
+        }
+    }
+}
+
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker { RawWaker::new(ptr::null(), &VTABLE) }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // safe: the vtable never touches the data pointer
+    unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    #[test]
+    fn menus_in_a_row_dont_leave_anything_behind() {
+        let io = AsyncIO::new();
+        let screen = Screen::new(0, 0);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let redraws = [Rc::new(Cell::new(0)), Rc::new(Cell::new(0))];
+        for (i, count) in redraws.iter().enumerate() {
+            let count = count.clone();
+            let mut run = Box::pin(io.menu(move |_, menu| {
+                count.set(count.get() + 1);
+                menu.on_tick(|_| Signal::Break);
+            }));
+            (io.state.scene.borrow_mut())(&screen);
+            assert_eq!(redraws[i].get(), 1);
+
+            io.state.event.set(Some(InputEvent::Tick(1)));
+            assert_eq!(run.as_mut().poll(&mut cx), Poll::Ready(()));
+
+            // the finished menu is off the screen, and a stray event goes nowhere
+            (io.state.scene.borrow_mut())(&screen);
+            assert_eq!(redraws[i].get(), 1);
+            assert!(run.menu.handle(InputEvent::Tick(2)).is_none());
+        }
+
+        // a later draw() isn't undone by the menu going away
+        let drawn = Rc::new(Cell::new(false));
+        let d = drawn.clone();
+        let run = io.menu(|_, _| {});
+        io.draw(move |_| d.set(true));
+        drop(run);
+        (io.state.scene.borrow_mut())(&screen);
+        assert!(drawn.get());
+    }
+}
@@ -0,0 +1,53 @@
+use crate::aliases::*;
+
+// Collects damaged cells into rectangles: runs along each row, merged downward
+// with identical runs from the row above. Cells must arrive in row-major order.
+pub(crate) struct Damage {
+    done: Vec<CellRect>,
+    open: Vec<CellRect>,  // rects that touch the previous row
+    runs: Vec<(isize, isize)>,  // (min_x, max_x) in the current row
+    y: isize,
+}
+
+impl Damage {
+    pub fn new() -> Damage {
+        Damage { done: vec![], open: vec![], runs: vec![], y: 0 }
+    }
+
+    pub fn touch(&mut self, xy: CellPoint) {
+        if xy.y != self.y {
+            self.end_row();
+            self.y = xy.y;
+        }
+
+        if let Some(run) = self.runs.last_mut() {
+            if run.1 == xy.x { run.1 += 1; return }
+        }
+        self.runs.push((xy.x, xy.x + 1));
+    }
+
+    fn end_row(&mut self) {
+        let y = self.y;
+        let mut still_open = vec![];
+        for (x0, x1) in self.runs.drain(..) {
+            let above = self.open.iter().position(|r| 
+                r.min_x() == x0 && r.max_x() == x1 && r.max_y() == y
+            );
+            if let Some(ix) = above {
+                let mut r = self.open.remove(ix);
+                r.size.height += 1;
+                still_open.push(r);
+            } else {
+                still_open.push(rect(x0, y, x1 - x0, 1));
+            }
+        }
+        self.done.append(&mut self.open);
+        self.open = still_open;
+    }
+
+    pub fn finish(mut self) -> Vec<CellRect> {
+        self.end_row();
+        self.done.append(&mut self.open);
+        self.done
+    }
+}
@@ -0,0 +1,126 @@
+use std::any::Any;
+
+use crate::{Interactor, Screen, Signal, Stamp};
+
+use super::{InputEvent, Menu, MouseEvent, menu::MenuState};
+
+// Sources and targets are registered on every redraw like any other handler,
+// but the drag in progress lives on the menu, so it survives the redraws that happen
+// while the ghost follows the pointer around.
+pub(super) struct DragSource {
+    interactor: Interactor,
+    payload: Option<Box<dyn Any>>,
+    ghost: Option<Stamp>,
+}
+
+type Accept<'a> = Box<dyn 'a+Fn(&dyn Any) -> bool>;
+
+pub(super) struct DropTarget<'a> {
+    interactor: Interactor,
+    accept: Accept<'a>,
+    on_drop: Box<dyn 'a+FnMut(Box<dyn Any>) -> Signal>,
+}
+
+pub(super) struct ActiveDrag {
+    source: Interactor,
+    payload: Box<dyn Any>,
+    ghost: Stamp,
+    over: Option<(Interactor, bool)>,  // bool: will it take the payload?
+}
+
+impl<'a> Menu<'a> {
+    /// Dragging `interactor` picks up `payload`. `ghost` is drawn at the pointer until it's dropped.
+    pub fn drag_source<T: 'static>(&self, interactor: Interactor, payload: T, ghost: Stamp) {
+        self.state.drag_sources.borrow_mut().push(DragSource { 
+            interactor, payload: Some(Box::new(payload)), ghost: Some(ghost),
+        });
+    }
+
+    /// `interactor` takes payloads of type `T` that pass `accept`. Anything else is refused.
+    pub fn drop_target<T: 'static>(
+        &self, interactor: Interactor, 
+        accept: impl 'a+Fn(&T) -> bool, 
+        mut on_drop: impl 'a+FnMut(T) -> Signal,
+    ) {
+        self.state.drop_targets.borrow_mut().push(DropTarget {
+            interactor,
+            accept: Box::new(move |p| p.downcast_ref::<T>().is_some_and(&accept)),
+            on_drop: Box::new(move |p| match p.downcast::<T>() {
+                Ok(p) => on_drop(*p),
+                Err(_) => Signal::Continue,
+            }),
+        });
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.state.drag.borrow().is_some()
+    }
+
+    /// The source of the drag in progress.
+    pub fn drag_source_interactor(&self) -> Option<Interactor> {
+        self.state.drag.borrow().as_ref().map(|d| d.source)
+    }
+
+    /// The target under the pointer, and whether it would accept the drop. Use it to highlight targets.
+    pub fn drag_over(&self) -> Option<(Interactor, bool)> {
+        self.state.drag.borrow().as_ref().and_then(|d| d.over)
+    }
+
+    pub(crate) fn draw_drag_ghost(&self, screen: &Screen) {
+        if let Some(d) = self.state.drag.borrow().as_ref() {
+            d.ghost.draw_without_interactors(screen.brush().offset_rect(self.mouse_xy().to_vector()));
+        }
+    }
+}
+
+impl<'a> MenuState<'a> {
+    // Some(_) if drag and drop took the event
+    pub(super) fn handle_drag_drop(&self, i: InputEvent) -> Option<Signal> {
+        match i {
+            InputEvent::Mouse(MouseEvent::Drag { start_interactor, now_point, now_interactor, .. }) => {
+                self.mouse_xy.replace(now_point);
+                if self.drag.borrow().is_none() {
+                    let mut sources = self.drag_sources.borrow_mut();
+                    let source = sources.iter_mut().find(|s| s.interactor == start_interactor)?;
+                    let (payload, ghost) = (source.payload.take()?, source.ghost.take()?);
+                    self.drag.replace(Some(ActiveDrag { source: start_interactor, payload, ghost, over: None }));
+                }
+
+                let over = self.drop_targets.borrow().iter()
+                    .find(|t| t.interactor == now_interactor)
+                    .map(|t| {
+                        let drag = self.drag.borrow();
+                        (t.interactor, (t.accept)(&*drag.as_ref().unwrap().payload))
+                    });
+                self.drag.borrow_mut().as_mut().unwrap().over = over;
+
+                // the source still sees its own drag, but the ghost has to follow the pointer
+                match self.send(start_interactor, i) {
+                    None | Some(Signal::Continue) | Some(Signal::Refresh) => Some(Signal::Refresh),
+                    s => s,
+                }
+            }
+            InputEvent::Mouse(MouseEvent::Up(_, _, interactor, _)) => {
+                let drag = self.drag.borrow_mut().take()?;
+                let mut targets = self.drop_targets.borrow_mut();
+                let sig = match targets.iter_mut().find(|t| t.interactor == interactor) {
+                    Some(t) if (t.accept)(&*drag.payload) => (t.on_drop)(drag.payload),
+                    _ => Signal::Continue,
+                };
+                // the ghost has to go away
+                match sig {
+                    Signal::Continue => Some(Signal::Refresh),
+                    s => Some(s),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    // whatever comes next -- a modal, or the next menu -- mustn't get this menu's drop
+    pub(super) fn end_drag_if_leaving(&self, sig: &Option<Signal>) {
+        if let Some(Signal::Modal(_)) | Some(Signal::Break) = sig {
+            self.drag.replace(None);
+        }
+    }
+}
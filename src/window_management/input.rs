@@ -20,10 +20,27 @@ impl InputEvent {
     }
 }
 
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub super_key: bool,  // windows key, command key
+}
+
+impl Modifiers {
+    pub fn none() -> Modifiers {
+        Modifiers { shift: false, control: false, alt: false, super_key: false }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MouseEvent {
-    Click(MouseButton, CellPoint, Interactor),
-    Up(MouseButton, CellPoint, Interactor),
+    Click(MouseButton, CellPoint, Interactor, Modifiers),
+    // these come right after the second and third Click
+    DoubleClick(MouseButton, CellPoint, Interactor, Modifiers),
+    TripleClick(MouseButton, CellPoint, Interactor, Modifiers),
+    Up(MouseButton, CellPoint, Interactor, Modifiers),
     Drag { 
         mouse_button: MouseButton, 
         start_point: CellPoint, 
@@ -31,7 +48,17 @@ pub enum MouseEvent {
         last_point: CellPoint,
         last_interactor: Interactor,
         now_point: CellPoint,
-        now_interactor: Interactor
+        now_interactor: Interactor,
+        modifiers: Modifiers,
+    },
+    // after the Up that ends a drag that actually went somewhere
+    DragEnd {
+        mouse_button: MouseButton,
+        start_point: CellPoint,
+        start_interactor: Interactor,
+        now_point: CellPoint,
+        now_interactor: Interactor,
+        modifiers: Modifiers,
     },
     Wiggle { 
         last_point: CellPoint,
@@ -39,28 +66,50 @@ pub enum MouseEvent {
         now_point: CellPoint,
         now_interactor: Interactor
     },
-    Scroll(f32, CellPoint, Interactor),
+    Scroll(f32, CellPoint, Interactor, Modifiers),
+    HoverEnter(CellPoint, Interactor),
+    HoverHold(CellPoint, Interactor),  // the pointer stayed put for IO's hover delay
+    HoverLeave(CellPoint, Interactor),
     // wheel?
 }
 
 impl MouseEvent {
     pub fn offset(&self, vec: CellVector) -> MouseEvent {
         match *self {
-            MouseEvent::Click(mb, cp, int) => 
-                MouseEvent::Click(mb, cp + vec, int),
-            MouseEvent::Up(mb, cp, int) => 
-                MouseEvent::Up(mb, cp + vec, int),
+            MouseEvent::Click(mb, cp, int, mods) => 
+                MouseEvent::Click(mb, cp + vec, int, mods),
+            MouseEvent::DoubleClick(mb, cp, int, mods) => 
+                MouseEvent::DoubleClick(mb, cp + vec, int, mods),
+            MouseEvent::TripleClick(mb, cp, int, mods) => 
+                MouseEvent::TripleClick(mb, cp + vec, int, mods),
+            MouseEvent::Up(mb, cp, int, mods) => 
+                MouseEvent::Up(mb, cp + vec, int, mods),
             MouseEvent::Drag {
                 mouse_button, 
                 start_point, start_interactor,
                 last_point, last_interactor,
                 now_point, now_interactor,
+                modifiers,
             } => {
                 MouseEvent::Drag {
                     mouse_button, 
                     start_point: start_point + vec, start_interactor,
                     last_point: last_point + vec, last_interactor,
                     now_point: now_point + vec, now_interactor,
+                    modifiers,
+                }
+            }
+            MouseEvent::DragEnd {
+                mouse_button, 
+                start_point, start_interactor,
+                now_point, now_interactor,
+                modifiers,
+            } => {
+                MouseEvent::DragEnd {
+                    mouse_button, 
+                    start_point: start_point + vec, start_interactor,
+                    now_point: now_point + vec, now_interactor,
+                    modifiers,
                 }
             }
             MouseEvent::Wiggle {
@@ -72,19 +121,25 @@ impl MouseEvent {
                     now_point: now_point + vec, now_interactor,
                 }
             }
-            MouseEvent::Scroll(amt, cp, int) => 
-                MouseEvent::Scroll(amt, cp + vec, int),
+            MouseEvent::Scroll(amt, cp, int, mods) => 
+                MouseEvent::Scroll(amt, cp + vec, int, mods),
+            MouseEvent::HoverEnter(cp, int) => 
+                MouseEvent::HoverEnter(cp + vec, int),
+            MouseEvent::HoverHold(cp, int) => 
+                MouseEvent::HoverHold(cp + vec, int),
+            MouseEvent::HoverLeave(cp, int) => 
+                MouseEvent::HoverLeave(cp + vec, int),
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, Enum, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MouseButton {
-    Left, Right
+    Left, Middle, Right
 }
 
 impl MouseButton {
-    pub const ALL: [MouseButton; 2] = [MouseButton::Left, MouseButton::Right];
+    pub const ALL: [MouseButton; 3] = [MouseButton::Left, MouseButton::Middle, MouseButton::Right];
 }
 
 // TODO: Add an "is_accept()" method that returns true for enter and space
@@ -100,6 +155,22 @@ pub struct KeyCombo {
     pub code: Keycode,
     pub shift: bool,
     pub control: bool,
+    pub alt: bool,
+    pub super_key: bool,
+}
+
+impl KeyCombo {
+    pub fn new(code: Keycode, modifiers: Modifiers) -> KeyCombo {
+        KeyCombo { 
+            code, 
+            shift: modifiers.shift, control: modifiers.control, 
+            alt: modifiers.alt, super_key: modifiers.super_key,
+        }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers { shift: self.shift, control: self.control, alt: self.alt, super_key: self.super_key }
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
@@ -130,6 +201,12 @@ pub enum Keycode {
     
     Space, Tab,
 
+    // only sent if IO::set_distinguish_numpad is on. otherwise numpad keys come in as 
+    // the digits and symbols on them
+    NumPad0, NumPad1, NumPad2, NumPad3, NumPad4,
+    NumPad5, NumPad6, NumPad7, NumPad8, NumPad9,
+    NumPadDot, NumPadSlash, NumPadAsterisk, NumPadMinus, NumPadPlus, NumPadEnter,
+
     // TODO: Shift punctuation
     Tilde,
     Exclamation, At, Pound, Dollar, Percent, Caret, Ampersand, Asterisk,
@@ -142,8 +219,31 @@ pub enum Keycode {
     // don't include Lock, Shift, Alt, Super, and Ctrl -- terminals don't respond to 
     // these by themselves
 
-    // don't expose NumPad keys separately: terminals don't know the difference
-    // and doing so encourages developers to make UIs that won't work on most laptops
+    // NumPad keys are opt-in: terminals don't know the difference, and most
+    // laptops don't have a numpad, so bind something else alongside them
+}
+impl Keycode {
+    // everything but Unknown
+    pub const ALL: [Keycode; 116] = {
+        use Keycode::*;
+        [
+            Key0, Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, A, B, C, D, E, F, G, H, I,
+            J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, F1, F2, F3, F4, F5, F6, F7, F8, F9,
+            F10, F11, F12, F13, F14, F15, Down, Left, Right, Up, Apostrophe, Backquote, Backslash,
+            Comma, Equal, LeftBracket, Minus, Period, RightBracket, Semicolon, Slash, Backspace,
+            Delete, End, Enter, Escape, Home, Insert, Menu, PageDown, PageUp, Pause, Space, Tab,
+            NumPad0, NumPad1, NumPad2, NumPad3, NumPad4, NumPad5, NumPad6, NumPad7, NumPad8,
+            NumPad9, NumPadDot, NumPadSlash, NumPadAsterisk, NumPadMinus, NumPadPlus, NumPadEnter,
+            Tilde, Exclamation, At, Pound, Dollar, Percent, Caret, Ampersand, Asterisk, LeftParen,
+            RightParen, Underscore, Plus, LeftBrace, RightBrace, Pipe, Colon, DoubleQuote, LessThan,
+            GreaterThan, QuestionMark,
+        ]
+    };
+
+    /// The reverse of `{:?}`.
+    pub fn from_name(name: &str) -> Option<Keycode> {
+        Keycode::ALL.iter().find(|k| format!("{:?}", k).eq_ignore_ascii_case(name)).copied()
+    }
 }
 impl KeyEvent {
     pub fn alter_combo(&mut self, alter: impl FnOnce(&mut KeyCombo)) {
@@ -174,3 +274,18 @@ impl KeyEvent {
         return !self.is_down()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keycode_names_round_trip() {
+        for code in Keycode::ALL.iter() {
+            assert_eq!(Keycode::from_name(&format!("{:?}", code)), Some(*code));
+        }
+        assert_eq!(Keycode::from_name("numpad8"), Some(Keycode::NumPad8));
+        assert_eq!(Keycode::from_name("Unknown"), None);
+        assert_eq!(Keycode::from_name(""), None);
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use crate::{Brush, Interactor, Signal};
+
+use super::{InputEvent, KeyCombo, KeyEvent, Keycode, Menu, Modifiers, menu::{Handler, MenuState}, ticks_in};
+
+pub(super) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+// The keys typed so far live on the menu, not in the handlers, so a half-typed
+// sequence survives the redraw that shows it in the indicator.
+impl<'a> Menu<'a> {
+    /// Fires when the keys in `sequence` are pressed one after another, like `g g` or `Ctrl+X Ctrl+S`.
+    /// While a prefix is pending, its keys don't go anywhere else.
+    pub fn on_key_sequence(&self, sequence: &[KeyCombo], cb: impl 'a+FnMut(KeyEvent) -> Signal) {
+        self.state.on_key_sequence(sequence, cb)
+    }
+
+    /// How long to wait for the next key of a sequence. One second by default.
+    pub fn set_sequence_timeout(&self, timeout: Duration) {
+        self.state.sequence_timeout.set(timeout);
+    }
+
+    /// The start of a sequence typed so far. Empty most of the time.
+    pub fn pending_keys(&self) -> Vec<KeyCombo> {
+        self.state.pending.borrow().clone()
+    }
+
+    /// Draw something like `Ctrl+X -` if a sequence is half typed.
+    pub fn draw_pending_keys(&self, b: Brush) {
+        let pending = self.pending_keys();
+        if pending.is_empty() { return }
+        let keys: Vec<String> = pending.iter().map(|k| k.to_string()).collect();
+        b.putfs(&format!("{} -", keys.join(" ")));
+    }
+}
+
+impl KeyCombo {
+    /// Space-separated, like `Ctrl+X Ctrl+S`.
+    pub fn parse_sequence(s: &str) -> Option<Vec<KeyCombo>> {
+        s.split_whitespace().map(KeyCombo::parse).collect()
+    }
+}
+
+impl<'a> MenuState<'a> {
+    fn on_key_sequence(&self, sequence: &[KeyCombo], mut cb: impl 'a+FnMut(KeyEvent) -> Signal) {
+        if sequence.is_empty() { return }
+        let mut hndl = self.handlers.borrow_mut();
+        let ix = hndl.len();
+        hndl.push(Handler(Box::new(move |input| {
+            match input {
+                InputEvent::Keyboard(k) => { cb(k) }
+                _ => unreachable!(),
+            }
+        })));
+        self.sequences.borrow_mut().push((sequence.to_vec(), Interactor::from_index(ix)));
+    }
+
+    // Some(_) if the sequence machinery took the event
+    pub(super) fn handle_key_sequence(&self, i: InputEvent) -> Option<Signal> {
+        let k = match i {
+            InputEvent::Keyboard(KeyEvent::Press(k)) => k,
+            // the text half of a key that goes into a sequence. The keyboard queues it ahead of
+            // the Press, so decide from the text itself
+            InputEvent::Keyboard(KeyEvent::Type(c)) => {
+                let k = typed_combo(c)?;
+                let taken = self.continues_sequence(k) || self.sequences.borrow().iter().any(|(s, _)| s[0] == k);
+                return if taken { Some(Signal::Continue) } else { None }
+            }
+            _ => return None,
+        };
+
+        let was_pending = !self.pending.borrow().is_empty();
+        let mut typed = self.pending.borrow().clone();
+        typed.push(k);
+
+        // if the key doesn't continue what's pending, it might still start something new
+        for attempt in [typed, vec![k]] {
+            let sequences = self.sequences.borrow();
+            if let Some((_, interactor)) = sequences.iter().find(|(s, _)| *s == attempt) {
+                self.pending.borrow_mut().clear();
+                let interactor = *interactor;
+                drop(sequences);
+                return Some(self.send(interactor, i).unwrap_or(Signal::Refresh))
+            }
+            if sequences.iter().any(|(s, _)| s.len() > attempt.len() && s.starts_with(&attempt)) {
+                self.pending.replace(attempt);
+                self.pending_since.set(self.last_tick.get());
+                return Some(Signal::Refresh)
+            }
+        }
+
+        // a dead end: forget the prefix, and let the key go where it would have gone
+        self.pending.borrow_mut().clear();
+        if was_pending { self.pending_dropped.set(true) }
+        None
+    }
+
+    fn continues_sequence(&self, k: KeyCombo) -> bool {
+        let pending = self.pending.borrow();
+        if pending.is_empty() { return false }
+        self.sequences.borrow().iter().any(|(s, _)| 
+            s.len() > pending.len() && s.starts_with(&pending) && s[pending.len()] == k
+        )
+    }
+
+    // true if a pending prefix just ran out of time
+    pub(super) fn expire_key_sequence(&self, tick: u64) -> bool {
+        self.last_tick.set(tick);
+        if self.pending.borrow().is_empty() { return false }
+        let timeout = ticks_in(self.sequence_timeout.get(), self.ticks_per_second.get());
+        if tick.saturating_sub(self.pending_since.get()) <= timeout { return false }
+        self.pending.borrow_mut().clear();
+        true
+    }
+}
+
+// the key that types `c`, as the keyboard would report its Press
+fn typed_combo(c: char) -> Option<KeyCombo> {
+    use Keycode::*;
+    let (code, shift) = match c {
+        'a'..='z' => (Keycode::from_name(&c.to_string())?, false),
+        'A'..='Z' => (Keycode::from_name(&c.to_string())?, true),
+        '0'..='9' => (Keycode::from_name(&format!("Key{}", c))?, false),
+        ' ' => (Space, false), '\'' => (Apostrophe, false), '`' => (Backquote, false),
+        '\\' => (Backslash, false), ',' => (Comma, false), '=' => (Equal, false),
+        '[' => (LeftBracket, false), '-' => (Minus, false), '.' => (Period, false),
+        ']' => (RightBracket, false), ';' => (Semicolon, false), '/' => (Slash, false),
+        // shifted punctuation comes in as its own keycode, without shift
+        '~' => (Tilde, false), '!' => (Exclamation, false), '@' => (At, false),
+        '#' => (Pound, false), '$' => (Dollar, false), '%' => (Percent, false),
+        '^' => (Caret, false), '&' => (Ampersand, false), '*' => (Asterisk, false),
+        '(' => (LeftParen, false), ')' => (RightParen, false), '_' => (Underscore, false),
+        '+' => (Plus, false), '{' => (LeftBrace, false), '}' => (RightBrace, false),
+        '|' => (Pipe, false), ':' => (Colon, false), '"' => (DoubleQuote, false),
+        '<' => (LessThan, false), '>' => (GreaterThan, false), '?' => (QuestionMark, false),
+        _ => return None,
+    };
+    Some(KeyCombo::new(code, Modifiers { shift, ..Modifiers::none() }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    fn key(s: &str) -> KeyCombo { KeyCombo::parse(s).unwrap() }
+    fn press(s: &str) -> InputEvent { InputEvent::Keyboard(KeyEvent::Press(key(s))) }
+    fn typed(c: char) -> InputEvent { InputEvent::Keyboard(KeyEvent::Type(c)) }
+
+    #[test]
+    fn parses_sequences() {
+        assert_eq!(KeyCombo::parse_sequence("Ctrl+X  Ctrl+S"), Some(vec![key("Ctrl+X"), key("Ctrl+S")]));
+        assert_eq!(KeyCombo::parse_sequence("G G"), Some(vec![key("G"), key("G")]));
+        assert_eq!(KeyCombo::parse_sequence("Ctrl+X Banana"), None);
+    }
+
+    #[test]
+    fn swallows_text_of_sequence_keys_only() {
+        let menu = MenuState::new();
+        let fired = Rc::new(Cell::new(0));
+        let f = fired.clone();
+        menu.on_key_sequence(&[key("G"), key("G")], move |_| { f.set(f.get() + 1); Signal::Continue });
+
+        // the keyboard sends the text first, then the press
+        for _ in 0..2 {
+            assert!(menu.handle_key_sequence(typed('g')).is_some());
+            assert!(menu.handle_key_sequence(press("G")).is_some());
+        }
+        assert_eq!(fired.get(), 1);
+        assert!(menu.pending.borrow().is_empty());
+
+        // the next unrelated character still gets through, text and all
+        assert!(menu.handle_key_sequence(typed('x')).is_none());
+        assert!(menu.handle_key_sequence(press("X")).is_none());
+
+        // a dead end gives up the prefix and passes the key along
+        assert!(menu.handle_key_sequence(typed('g')).is_some());
+        assert!(menu.handle_key_sequence(press("G")).is_some());
+        assert!(menu.handle_key_sequence(typed('x')).is_none());
+        assert!(menu.handle_key_sequence(press("X")).is_none());
+        assert!(menu.pending.borrow().is_empty());
+        assert_eq!(fired.get(), 1);
+    }
+}
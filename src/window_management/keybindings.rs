@@ -0,0 +1,282 @@
+use std::{fmt, fs, io::{self, Write}, path::Path};
+
+use euclid::point2;
+
+use crate::{IO, colors};
+
+use super::{KeyCombo, KeyEvent, KeyRecognizer, Keycode, Modifiers};
+
+// Actions keep the order they were defined in, so the controls screen
+// lists them in the order you'd explain them.
+#[derive(Clone, Debug)]
+pub struct Keybindings {
+    actions: Vec<Action>,
+}
+
+#[derive(Clone, Debug)]
+struct Action {
+    name: String,
+    label: String,
+    keys: Vec<KeyCombo>,
+    defaults: Vec<KeyCombo>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self { Keybindings::new() }
+}
+
+impl Keybindings {
+    pub fn new() -> Keybindings {
+        Keybindings { actions: vec![] }
+    }
+
+    /// Add an action, or reset an existing one. `label` is what the controls screen calls it.
+    pub fn define(&mut self, name: &str, label: &str, defaults: &[KeyCombo]) {
+        let action = Action { 
+            name: name.to_string(), label: label.to_string(), 
+            keys: defaults.to_vec(), defaults: defaults.to_vec(),
+        };
+        match self.actions.iter_mut().find(|a| a.name == name) {
+            Some(a) => *a = action,
+            None => self.actions.push(action),
+        }
+    }
+
+    pub fn keys(&self, name: &str) -> &[KeyCombo] {
+        self.actions.iter().find(|a| a.name == name).map_or(&[], |a| &a.keys)
+    }
+
+    // does nothing if the action was never defined
+    pub fn bind(&mut self, name: &str, keys: &[KeyCombo]) {
+        if let Some(a) = self.actions.iter_mut().find(|a| a.name == name) {
+            a.keys = keys.to_vec();
+        }
+    }
+
+    pub fn reset_to_defaults(&mut self) {
+        for a in self.actions.iter_mut() { a.keys = a.defaults.clone() }
+    }
+
+    /// Matches presses of any key bound to `name`, as bound right now.
+    pub fn recognizer(&self, name: &str) -> KeyRecognizer<'static> {
+        let keys = self.keys(name).to_vec();
+        KeyRecognizer(Box::new(move |key| match key {
+            KeyEvent::Press(k) => keys.contains(&k),
+            _ => false,
+        }))
+    }
+
+    /// Every key bound to more than one action, with the actions it's bound to.
+    pub fn conflicts(&self) -> Vec<(KeyCombo, Vec<String>)> {
+        let mut conflicts: Vec<(KeyCombo, Vec<String>)> = vec![];
+        for a in self.actions.iter() {
+            for k in a.keys.iter() {
+                let users: Vec<String> = self.actions.iter()
+                    .filter(|b| b.keys.contains(k))
+                    .map(|b| b.name.clone())
+                    .collect();
+                if users.len() > 1 && !conflicts.iter().any(|(c, _)| c == k) {
+                    conflicts.push((*k, users));
+                }
+            }
+        }
+        conflicts
+    }
+
+    // one line per action: `move_north = Up, NumPad8, Shift+K`
+    pub fn to_config(&self) -> String {
+        let mut out = String::new();
+        for a in self.actions.iter() {
+            let keys: Vec<String> = a.keys.iter().map(|k| k.to_string()).collect();
+            out.push_str(&format!("{} = {}\n", a.name, keys.join(", ")));
+        }
+        out
+    }
+
+    /// Only actions you've already defined get read. Others are skipped, so old config files still load.
+    /// If any line is bad, nothing changes.
+    pub fn load_config(&mut self, config: &str) -> io::Result<()> {
+        let mut parsed = vec![];
+        for (ix, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+
+            let bad = |why: &str| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", ix + 1, why));
+            let (name, keys) = line.split_once('=').ok_or_else(|| bad("expected `action = keys`"))?;
+            let keys = keys.split(',')
+                .map(|k| k.trim())
+                .filter(|k| !k.is_empty())
+                .map(|k| KeyCombo::parse(k).ok_or_else(|| bad(&format!("unknown key {:?}", k))))
+                .collect::<io::Result<Vec<_>>>()?;
+            parsed.push((name.trim(), keys));
+        }
+
+        for (name, keys) in parsed { self.bind(name, &keys) }
+        Ok(())
+    }
+
+    /// Writes a temporary file next to `path` and renames it over, so a crash halfway 
+    /// leaves the old file alone.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file path"))?;
+        let mut tmp_name = name.to_os_string();
+        tmp_name.push(".tmp");
+        let tmp = path.with_file_name(tmp_name);
+
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(self.to_config().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    }
+
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.load_config(&fs::read_to_string(path)?)
+    }
+}
+
+impl KeyCombo {
+    /// Reads what Display writes: `Ctrl+Shift+S`, `Alt+NumPad8`, `Up`.
+    pub fn parse(s: &str) -> Option<KeyCombo> {
+        let mut parts: Vec<&str> = s.split('+').map(|p| p.trim()).collect();
+
+        let code = Keycode::from_name(parts.pop()?)?;
+        let mut modifiers = Modifiers::none();
+        for p in parts {
+            match p.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.control = true,
+                "shift" => modifiers.shift = true,
+                "alt" => modifiers.alt = true,
+                "super" => modifiers.super_key = true,
+                _ => return None,
+            }
+        }
+        Some(KeyCombo::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.control { write!(f, "Ctrl+")? }
+        if self.alt { write!(f, "Alt+")? }
+        if self.super_key { write!(f, "Super+")? }
+        if self.shift { write!(f, "Shift+")? }
+        write!(f, "{:?}", self.code)
+    }
+}
+
+impl IO {
+    /// A screen listing every action and its keys. Up and down pick an action, enter adds
+    /// the next key pressed to it, delete unbinds it, backspace puts everything back, and escape leaves.
+    pub fn controls_screen(&mut self, bindings: &mut Keybindings) {
+        let mut selected: usize = 0;
+        let mut capturing = false;
+        loop {
+            let redraw = |out: &crate::Screen| {
+                use colors::*;
+                let conflicts = bindings.conflicts();
+                let b = out.brush();
+                b.fill(crate::FSem::new().color((Dark[0], Light[2])));
+                b.at(point2(2, 1)).fg(White).putfs("CONTROLS");
+                let hint = if capturing { "Press a key for this action..." } 
+                    else { "Enter: add key   Delete: clear   Backspace: defaults   Escape: done" };
+                b.at(point2(2, out.rect().height() - 3)).fg(Light[0]).putfs(hint);
+
+                // Normal font rows are two cells tall
+                let rows = ((out.rect().height() - 8) / 2).max(1) as usize;
+                let first = selected.saturating_sub(rows - 1);
+                for (i, a) in bindings.actions.iter().enumerate().skip(first).take(rows) {
+                    let y = 4 + 2 * (i - first) as isize;
+                    let clash = a.keys.iter().any(|k| conflicts.iter().any(|(c, _)| c == k));
+                    let (bg, fg) = if i == selected { (Light[2], Dark[0]) } else { (Dark[0], Light[2]) };
+                    let row = b.color((bg, fg));
+                    let mut keys = a.keys.iter().map(|k| k.to_string()).collect::<Vec<_>>();
+                    if capturing && i == selected { keys.push("...".to_string()) }
+                    let keys = keys.join(", ");
+                    row.at(point2(2, y)).putfs(&a.label);
+                    row.at(point2(32, y)).fg(if clash { LtRed[2] } else { fg }).putfs(&keys);
+                }
+            };
+
+            let key = self.getch(redraw);
+            let press = if let KeyEvent::Press(k) = key { k } else { continue };
+            if capturing {
+                capturing = false;
+                if press.code != Keycode::Escape {
+                    if let Some(a) = bindings.actions.get_mut(selected) { 
+                        if !a.keys.contains(&press) { a.keys.push(press) }
+                    }
+                }
+                continue
+            }
+
+            match press.code {
+                Keycode::Up => selected = selected.saturating_sub(1),
+                Keycode::Down => if selected + 1 < bindings.actions.len() { selected += 1 },
+                Keycode::Enter if !bindings.actions.is_empty() => capturing = true,
+                Keycode::Delete => if let Some(a) = bindings.actions.get_mut(selected) { a.keys.clear() },
+                Keycode::Backspace => bindings.reset_to_defaults(),
+                Keycode::Escape => return,
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combo_round_trips_through_text() {
+        let combo = KeyCombo::new(Keycode::S, Modifiers { shift: true, control: true, alt: false, super_key: false });
+        assert_eq!(combo.to_string(), "Ctrl+Shift+S");
+        assert_eq!(KeyCombo::parse("Ctrl+Shift+S"), Some(combo));
+        assert_eq!(KeyCombo::parse(" shift + control + s "), Some(combo));
+
+        for code in Keycode::ALL.iter() {
+            let k = KeyCombo::new(*code, Modifiers::none());
+            assert_eq!(KeyCombo::parse(&k.to_string()), Some(k));
+        }
+    }
+
+    #[test]
+    fn combo_rejects_malformed_text() {
+        assert_eq!(KeyCombo::parse(""), None);
+        assert_eq!(KeyCombo::parse("Ctrl+"), None);
+        assert_eq!(KeyCombo::parse("Hyper+S"), None);
+        assert_eq!(KeyCombo::parse("Ctrl+Banana"), None);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("chiropterm-keys-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("keys.cfg");
+
+        let mut bindings = Keybindings::new();
+        bindings.define("north", "Move north", &[KeyCombo::parse("Up").unwrap(), KeyCombo::parse("Ctrl+K").unwrap()]);
+        bindings.save(&path).unwrap();
+        assert!(!dir.join("keys.cfg.tmp").exists());
+
+        let mut loaded = Keybindings::new();
+        loaded.define("north", "Move north", &[]);
+        loaded.load(&path).unwrap();
+        assert_eq!(loaded.keys("north"), bindings.keys("north"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bad_config_changes_nothing() {
+        let mut bindings = Keybindings::new();
+        bindings.define("north", "Move north", &[KeyCombo::parse("Up").unwrap()]);
+        bindings.define("south", "Move south", &[KeyCombo::parse("Down").unwrap()]);
+
+        assert!(bindings.load_config("north = K\nsouth = Banana\n").is_err());
+        assert_eq!(bindings.keys("north"), &[KeyCombo::parse("Up").unwrap()]);
+
+        bindings.load_config("# comment\nnorth = K, NumPad8\nwest = H\n").unwrap();
+        assert_eq!(bindings.keys("north"), &[KeyCombo::parse("K").unwrap(), KeyCombo::parse("NumPad8").unwrap()]);
+        assert_eq!(bindings.keys("south"), &[KeyCombo::parse("Down").unwrap()]);
+    }
+}
@@ -2,15 +2,19 @@ use std::{cell::RefCell, collections::{BTreeMap, VecDeque}, rc::Rc};
 
 use minifb::{Key as MinifbKey, Window};
 
-use super::{KeyCombo, input::{KeyEvent, Keycode}};
+use super::{KeyCombo, input::{KeyEvent, Keycode, Modifiers}};
 
 pub(crate) struct Keyboard {
-    correlator: KeyCorrelatorRef
+    correlator: KeyCorrelatorRef,
+    pub distinguish_numpad: bool,
 }
 
 impl Keyboard {
     pub fn new() -> Self {
-        Keyboard { correlator: KeyCorrelatorRef(Rc::new(RefCell::new(KeyCorrelator::new()))) }
+        Keyboard { 
+            correlator: KeyCorrelatorRef(Rc::new(RefCell::new(KeyCorrelator::new()))),
+            distinguish_numpad: false,
+        }
     }
 
     pub fn monitor_minifb_utf32(&mut self, window: &mut Window) {
@@ -20,9 +24,7 @@ impl Keyboard {
     pub fn add_keys(&mut self, window: &mut Window) {
         // pressed keys
         if let Some(keys_down) = window.get_keys() {  
-            let shift = window.is_key_down(MinifbKey::LeftShift) || window.is_key_down(MinifbKey::RightShift);
-            let control = window.is_key_down(MinifbKey::LeftCtrl) || window.is_key_down(MinifbKey::RightCtrl);
-            self.correlator.0.borrow_mut().add_keys(&keys_down, shift, control);
+            self.correlator.0.borrow_mut().add_keys(&keys_down, modifiers(window), self.distinguish_numpad);
         }
     }
 
@@ -31,6 +33,16 @@ impl Keyboard {
     }
 }
 
+pub(crate) fn modifiers(window: &Window) -> Modifiers {
+    let down = |a, b| window.is_key_down(a) || window.is_key_down(b);
+    Modifiers {
+        shift: down(MinifbKey::LeftShift, MinifbKey::RightShift),
+        control: down(MinifbKey::LeftCtrl, MinifbKey::RightCtrl),
+        alt: down(MinifbKey::LeftAlt, MinifbKey::RightAlt),
+        super_key: down(MinifbKey::LeftSuper, MinifbKey::RightSuper),
+    }
+}
+
 struct KeyCorrelatorRef(Rc<RefCell<KeyCorrelator>>);
 
 impl minifb::InputCallback for KeyCorrelatorRef {
@@ -42,16 +54,10 @@ impl minifb::InputCallback for KeyCorrelatorRef {
 #[derive(Debug)]
 struct KeyCorrelator {
     utf32_keys: VecDeque<u32>,  // keycode, age in frames
-    keys_down: BTreeMap<MinifbKey, MinifbKeyMode>,
+    keys_down: BTreeMap<MinifbKey, Modifiers>,
     events: VecDeque<KeyEvent>,
 }
 
-#[derive(Clone, Copy, Debug)]
-struct MinifbKeyMode {
-    shift: bool,
-    control: bool,
-}
-
 impl KeyCorrelator {
     fn new() -> Self {
         KeyCorrelator {
@@ -61,7 +67,7 @@ impl KeyCorrelator {
         }
     }
 
-    fn add_keys(&mut self, new_keys_down: &[MinifbKey], shift: bool, control: bool) {
+    fn add_keys(&mut self, new_keys_down: &[MinifbKey], modifiers: Modifiers, numpad: bool) {
         // TODO: Preserve order instead of always putting utf32 keys first?
         while let Some(u) = self.utf32_keys.pop_front() {
             let c = if let Some(c) = char::from_u32(u) { c } else {
@@ -71,9 +77,7 @@ impl KeyCorrelator {
             // see if this is a key where we infer a keycode from a typed character
             if let Some(theoretical_code) = most_likely_keycode(c) {
                 self.events.push_back(censor_unhelpful_features(
-                    KeyEvent::Press(KeyCombo {
-                        shift, control, code: theoretical_code,
-                    })
+                    KeyEvent::Press(KeyCombo::new(theoretical_code, modifiers))
                 ))
             }  else {
                 // still have to type it!!
@@ -84,35 +88,49 @@ impl KeyCorrelator {
         }
 
         for key in new_keys_down {
-            let code = if let Some(code) = minifb_to_keycode(*key) { code } else { continue };
+            let code = if let Some(code) = minifb_to_keycode(*key, numpad) { code } else { continue };
 
             if !self.keys_down.contains_key(key) {
                 // newly down
                 self.events.push_back(censor_unhelpful_features(
-                    KeyEvent::Press(KeyCombo { code, shift, control })
+                    KeyEvent::Press(KeyCombo::new(code, modifiers))
                 ));
             }
         }
         for (key, details) in self.keys_down.iter() {
-            let code = if let Some(code) = minifb_to_keycode(*key) { code } else { continue };
+            let code = if let Some(code) = minifb_to_keycode(*key, numpad) { code } else { continue };
 
             if !new_keys_down.contains(key) {
                 self.events.push_back(censor_unhelpful_features(
-                    KeyEvent::Release(KeyCombo { code, shift: details.shift, control: details.control })
+                    KeyEvent::Release(KeyCombo::new(code, *details))
                 ))
             }
         }
         self.keys_down.clear();
         for key in new_keys_down {
-            self.keys_down.insert(*key, MinifbKeyMode { shift, control });
+            self.keys_down.insert(*key, modifiers);
         }
     }
 }
 
-fn minifb_to_keycode(key: MinifbKey) -> Option<Keycode> {
+fn minifb_to_keycode(key: MinifbKey, numpad: bool) -> Option<Keycode> {
     use MinifbKey as M;
     use Keycode::*;
 
+    if numpad {
+        match key {
+            M::NumPad0 => return Some(NumPad0), M::NumPad1 => return Some(NumPad1), 
+            M::NumPad2 => return Some(NumPad2), M::NumPad3 => return Some(NumPad3), 
+            M::NumPad4 => return Some(NumPad4), M::NumPad5 => return Some(NumPad5),
+            M::NumPad6 => return Some(NumPad6), M::NumPad7 => return Some(NumPad7), 
+            M::NumPad8 => return Some(NumPad8), M::NumPad9 => return Some(NumPad9), 
+            M::NumPadDot => return Some(NumPadDot), M::NumPadSlash => return Some(NumPadSlash),
+            M::NumPadAsterisk => return Some(NumPadAsterisk), M::NumPadMinus => return Some(NumPadMinus),
+            M::NumPadPlus => return Some(NumPadPlus), M::NumPadEnter => return Some(NumPadEnter),
+            _ => {}
+        }
+    }
+
     Some(match key {
         M::Key0 => Key0, M::Key1 => Key1, M::Key2 => Key2, M::Key3 => Key3,
         M::Key4 => Key4, M::Key5 => Key5, M::Key6 => Key6, M::Key7 => Key7,
@@ -176,9 +194,9 @@ fn censor_unhelpful_features(mut key: KeyEvent) -> KeyEvent {
     // This just deals with a bunch of miscellaneous things bad input systems might do
     key = match key {
         KeyEvent::Type('\r'|'\n') => 
-            KeyEvent::Press(KeyCombo { code: Keycode::Enter, shift: false, control: false }),
+            KeyEvent::Press(KeyCombo::new(Keycode::Enter, Modifiers::none())),
         KeyEvent::Type('\t') => 
-            KeyEvent::Press(KeyCombo { code: Keycode::Tab, shift: false, control: false }),
+            KeyEvent::Press(KeyCombo::new(Keycode::Tab, Modifiers::none())),
         _ => key
     };
 
@@ -1,16 +1,18 @@
-use std::{cell::{Cell, RefCell}, rc::Rc};
+use std::{cell::{Cell, RefCell}, rc::Rc, time::Duration};
 
-use euclid::point2;
+use enum_map::EnumMap;
 
-use crate::{CellPoint, IO, rendering::Interactor};
+use euclid::{point2, size2};
 
-use super::{KeyEvent, MouseEvent, input::{InputEvent}};
+use crate::{Brush, CellPoint, CellRect, CellSize, FSem, IO, Screen, colors, rendering::Interactor};
+
+use super::{KeyCombo, KeyEvent, MouseButton, MouseEvent, drag_drop::{ActiveDrag, DragSource, DropTarget}, input::{InputEvent}, key_sequence::DEFAULT_TIMEOUT, DEFAULT_TICKS_PER_SECOND};
 
 // TODO: Clear all interactors in one stroke? Or uh, a sub-menu that generates the None interactor no matter what
 // You know, so you can draw a screen with all its menus disabled!
 
 pub struct Menu<'a> {
-    state: Rc<MenuState<'a>>,
+    pub(super) state: Rc<MenuState<'a>>,
 }
 
 impl<'a> Menu<'a> {
@@ -56,20 +58,98 @@ impl<'a> Menu<'a> {
         self.state.mouse_xy.get()
     }
 
+    /// True from the Click on `interactor` until the matching Up, wherever the pointer goes.
+    pub fn pressed(&self, interactor: Interactor) -> bool {
+        interactor != Interactor::none() && 
+            self.state.capture.get().values().any(|c| *c == interactor)
+    }
+
+    /// The interactor the pointer has rested on for the hover delay, if any.
+    pub fn hovering(&self) -> Option<Interactor> {
+        match self.state.hover.get() {
+            (i, true) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Once the pointer rests on `interactor`, draw a `size` panel near it, on top of everything else.
+    pub fn tooltip(&self, interactor: Interactor, size: CellSize, draw: impl 'a+Fn(Brush)) {
+        self.state.tooltips.borrow_mut().push(Tooltip { interactor, size, draw: Box::new(draw) });
+    }
+
+    pub fn tooltip_text(&self, interactor: Interactor, text: &str) {
+        let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as isize;
+        // Normal font lines are two cells tall
+        let size = size2(width + 2, lines.len() as isize * 2 + 2);
+        self.tooltip(interactor, size, move |b| {
+            b.fill(FSem::new().color((colors::Dark[1], colors::Light[3])));
+            b.bevel_w95((colors::Light[0], colors::Dark[0]));
+            let mut at = b.at(point2(1, 1));
+            for l in lines.iter() {
+                at = at.putfs(l).on_newline();
+            }
+        });
+    }
+
+    pub(crate) fn draw_tooltip(&self, screen: &Screen) {
+        let hovering = if let Some(h) = self.hovering() { h } else { return };
+        let tooltips = self.state.tooltips.borrow();
+        let tooltip = if let Some(t) = tooltips.iter().find(|t| t.interactor == hovering) { t } else { return };
+
+        // down and to the right of the pointer, unless that runs off the screen
+        let bounds = screen.rect();
+        let mouse = self.mouse_xy();
+        let place = |m: isize, len: isize, min: isize, max: isize| {
+            let after = m + 1;
+            let start = if after + len <= max { after } else { m - len };
+            start.min(max - len).max(min)
+        };
+        let x = place(mouse.x, tooltip.size.width, bounds.min_x(), bounds.max_x());
+        let y = place(mouse.y, tooltip.size.height, bounds.min_y(), bounds.max_y());
+        (tooltip.draw)(screen.brush().region(CellRect::new(point2(x, y), tooltip.size)));
+    }
+
+    pub(crate) fn set_tick_rate(&self, ticks_per_second: u32) {
+        self.state.ticks_per_second.set(ticks_per_second);
+    }
+
     pub(crate) fn handle(&self, i: InputEvent) -> Option<Signal> {
-        self.state.handle(i)
+        let sig = self.state.handle(i);
+        self.state.end_drag_if_leaving(&sig);
+        sig
     }
 }
 
 pub struct MenuState<'a> {
-    handlers: RefCell<Vec<Handler<'a>>>,
+    pub(super) handlers: RefCell<Vec<Handler<'a>>>,
     on_tick: RefCell<Option<Handler<'a>>>,
     hprio_key_recognizers: RefCell<Vec<(KeyRecognizer<'a>, Interactor)>>,
     lprio_key_recognizers: RefCell<Vec<(KeyRecognizer<'a>, Interactor)>>,
-    mouse_xy: Cell<CellPoint>,
+    pub(super) mouse_xy: Cell<CellPoint>,
+    hover: Cell<(Interactor, bool)>,  // bool: held long enough for a tooltip
+    tooltips: RefCell<Vec<Tooltip<'a>>>,
+    pub(super) drag_sources: RefCell<Vec<DragSource>>,
+    pub(super) drop_targets: RefCell<Vec<DropTarget<'a>>>,
+    pub(super) drag: RefCell<Option<ActiveDrag>>,
+    // whatever got the Click gets the Up too, even if you let go somewhere else
+    capture: Cell<EnumMap<MouseButton, Interactor>>,
+    pub(super) sequences: RefCell<Vec<(Vec<KeyCombo>, Interactor)>>,
+    pub(super) pending: RefCell<Vec<KeyCombo>>,
+    pub(super) pending_since: Cell<u64>,
+    pub(super) pending_dropped: Cell<bool>,  // a prefix was abandoned: the indicator needs redrawing
+    pub(super) sequence_timeout: Cell<Duration>,
+    pub(super) ticks_per_second: Cell<u32>,  // set by whatever runs the menu
+    pub(super) last_tick: Cell<u64>,
     // TODO: Key handlers again
 }
 
+struct Tooltip<'a> {
+    interactor: Interactor,
+    size: CellSize,
+    draw: Box<dyn 'a+Fn(Brush)>,
+}
+
 impl<'a> MenuState<'a> {
     pub fn new() -> MenuState<'a> {
         MenuState {
@@ -78,6 +158,19 @@ impl<'a> MenuState<'a> {
             hprio_key_recognizers: RefCell::new(vec![]),
             lprio_key_recognizers: RefCell::new(vec![]),
             mouse_xy: Cell::new(point2(-1, -1)),  // will be populated on first tick
+            hover: Cell::new((Interactor::none(), false)),
+            tooltips: RefCell::new(vec![]),
+            drag_sources: RefCell::new(vec![]),
+            drop_targets: RefCell::new(vec![]),
+            drag: RefCell::new(None),
+            capture: Cell::new(enum_map::enum_map! { _ => Interactor::none() }),
+            sequences: RefCell::new(vec![]),
+            pending: RefCell::new(vec![]),
+            pending_since: Cell::new(0),
+            pending_dropped: Cell::new(false),
+            sequence_timeout: Cell::new(DEFAULT_TIMEOUT),
+            ticks_per_second: Cell::new(DEFAULT_TICKS_PER_SECOND),
+            last_tick: Cell::new(0),
         }
     }
 
@@ -86,6 +179,21 @@ impl<'a> MenuState<'a> {
         self.on_tick.borrow_mut().take();
         self.hprio_key_recognizers.borrow_mut().clear();
         self.lprio_key_recognizers.borrow_mut().clear();
+        self.tooltips.borrow_mut().clear();
+        self.drag_sources.borrow_mut().clear();
+        self.drop_targets.borrow_mut().clear();
+        self.sequences.borrow_mut().clear();
+    }
+
+    pub(super) fn send(&self, interactor: Interactor, i: InputEvent) -> Option<Signal> {
+        let ix = interactor.index()?;
+        let mut hnd = self.handlers.borrow_mut();
+        if ix < hnd.len() { return Some((hnd[ix].0)(i)) }
+        None
+    }
+
+    fn has_tooltip(&self, interactor: Interactor) -> bool {
+        self.tooltips.borrow().iter().any(|t| t.interactor == interactor)
     }
 
     pub fn on_tick(&self, mut cb: impl 'a+FnMut(u64) -> Signal) {
@@ -158,15 +266,30 @@ impl<'a> MenuState<'a> {
     }
 
     pub(crate) fn handle(&self, i: InputEvent) -> Option<Signal> {
+        // let go of the capture even if drag and drop eats the Up
+        let captured = if let InputEvent::Mouse(MouseEvent::Up(mb, ..)) = i {
+            let mut capture = self.capture.get();
+            let captured = std::mem::replace(&mut capture[mb], Interactor::none());
+            self.capture.set(capture);
+            captured
+        } else { Interactor::none() };
+
+        if let Some(sig) = self.handle_drag_drop(i) { return Some(sig) }
+        if let Some(sig) = self.handle_key_sequence(i) { return Some(sig) }
+
         match i {
-            InputEvent::Tick(_) => {
+            InputEvent::Tick(t) => {
+                let expired = self.expire_key_sequence(t);
                 let mut on_tick = self.on_tick.borrow_mut();
-                if let Some(of) = on_tick.as_mut() {
-                    return Some((of.0)(i))
+                let sig = on_tick.as_mut().map(|of| (of.0)(i));
+                // the pending keys indicator has to go
+                if expired { 
+                    if let None | Some(Signal::Continue) = sig { return Some(Signal::Refresh) } 
                 }
-                None
+                sig
             }
             InputEvent::Keyboard(k) => { 
+                let dropped = self.pending_dropped.replace(false);
                 for (rec, interactor) in self.hprio_key_recognizers.borrow().iter().chain(self.lprio_key_recognizers.borrow().iter()) {
                     if rec.0(k) {
                         if let Some(ix) = interactor.index() {
@@ -175,9 +298,14 @@ impl<'a> MenuState<'a> {
                         }
                     }
                 }
+                if dropped { return Some(Signal::Refresh) }
                 None
             }
-            InputEvent::Mouse(MouseEvent::Click(_, _, interactor)) => {
+            InputEvent::Mouse(MouseEvent::Click(mb, _, interactor, _)) => {
+                let mut capture = self.capture.get();
+                capture[mb] = interactor;
+                self.capture.set(capture);
+
                 if let Some(ix) = interactor.index() {
                     let mut hnd = self.handlers.borrow_mut();
                     if ix < hnd.len() { return Some((hnd[ix].0)(i)); };
@@ -200,22 +328,52 @@ impl<'a> MenuState<'a> {
                 }
                 None
             }
-            InputEvent::Mouse(MouseEvent::Scroll(_, _, interactor)) => {
+            InputEvent::Mouse(MouseEvent::Scroll(_, _, interactor, _)) => {
                 if let Some(ix) = interactor.index() {
                     let mut hnd = self.handlers.borrow_mut();
                     if ix < hnd.len() { return Some((hnd[ix].0)(i)); };
                 }
                 None
             },
-            InputEvent::Mouse(_) => {
-                None
+            InputEvent::Mouse(MouseEvent::Up(..)) => {
+                // an Up with no Click in this menu is left over from an earlier one
+                self.send(captured, i)
+            }
+            InputEvent::Mouse(MouseEvent::DragEnd { start_interactor, .. }) => {
+                // the drag monitor held onto whatever got the Click
+                self.send(start_interactor, i)
+            }
+            InputEvent::Mouse(MouseEvent::DoubleClick(_, _, interactor, _)) |
+            InputEvent::Mouse(MouseEvent::TripleClick(_, _, interactor, _)) => {
+                self.send(interactor, i)
+            }
+            InputEvent::Mouse(MouseEvent::HoverEnter(_, interactor)) => {
+                self.hover.replace((interactor, false));
+                self.send(interactor, i)
+            }
+            InputEvent::Mouse(MouseEvent::HoverHold(_, interactor)) => {
+                self.hover.replace((interactor, true));
+                let sig = self.send(interactor, i);
+                // the tooltip needs drawing
+                if self.has_tooltip(interactor) { 
+                    if let None | Some(Signal::Continue) = sig { return Some(Signal::Refresh) }
+                }
+                sig
+            }
+            InputEvent::Mouse(MouseEvent::HoverLeave(_, interactor)) => {
+                let (was, held) = self.hover.replace((Interactor::none(), false));
+                let sig = self.send(interactor, i);
+                if was == interactor && held && self.has_tooltip(interactor) {
+                    if let None | Some(Signal::Continue) = sig { return Some(Signal::Refresh) }
+                }
+                sig
             }
         }
     }
 }
 
-struct Handler<'a> (
-    Box<dyn 'a+FnMut(InputEvent) -> Signal>,
+pub(super) struct Handler<'a> (
+    pub(super) Box<dyn 'a+FnMut(InputEvent) -> Signal>,
 );
 
 pub struct KeyRecognizer<'a> (
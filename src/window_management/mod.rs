@@ -1,36 +1,51 @@
+mod asynchronous;
+mod damage;
+mod drag_drop;
 mod input;
+mod key_sequence;
+mod keybindings;
 mod keyboard;
 mod math;
 mod menu;
+mod modal;
 mod mouse;
 mod on_key;
 mod redraw_tracking_screen;
+mod tween;
 
-use std::{collections::VecDeque, time::{Instant}};
+use std::{collections::VecDeque, rc::Rc, time::{Duration, Instant}};
 
 use euclid::{size2};
 use minifb::{Scale, ScaleMode, Window, WindowOptions};
 
-use crate::{drawing::Screen, rendering::{self, Interactor, Render, Swatch}, window_management::keyboard::Keyboard};
+use crate::{CellRect, drawing::Screen, rendering::{self, ColorMap, CrtBuffers, CrtConfig, Interactor, Render, Swatch}, window_management::keyboard::Keyboard};
 
-use self::{math::{calculate_aspect, default_window_size}, mouse::Mouse, redraw_tracking_screen::RedrawTrackingScreen};
+use self::{math::{calculate_aspect, default_window_size}, mouse::Mouse, redraw_tracking_screen::RedrawTrackingScreen, tween::Clock};
 pub(crate) use self::math::Aspect;
 
+pub use asynchronous::{AsyncIO, MenuRun, NextEvent, Sleep};
 pub use menu::{Menu, KeyRecognizer, Signal};
+pub use modal::{Modal, Resolve};
 
 pub use self::math::AspectConfig;
 pub use input::*;
+pub use keybindings::Keybindings;
 pub use on_key::*;
+pub use tween::{Easing, Tween};
 
-const TICKS_PER_SECOND: usize = 30;
-const APPARENT_TICK_MICROSECONDS: u128 =  33333;  // 30 FPS
-const HANDLE_INPUT_EVERY: usize = 4166; // 240 FPS
+pub(crate) const DEFAULT_TICKS_PER_SECOND: u32 = 30;
+const DEFAULT_INPUT_PER_SECOND: u32 = 240;
 
 pub struct IO {
     // user vars
     iteration: u64,
     tick: u64,
     last_tick_at: Option<Instant>,
+    clock: Rc<Clock>,
+    ticks_per_second: u32,
+    input_per_second: u32,
+    max_catch_up: Option<u32>,  // None: not fixed-timestep
+    dropped_ticks: u64,
     window_title: String,
     aspect_config: AspectConfig,
 
@@ -40,6 +55,7 @@ pub struct IO {
     mouse: Mouse,
     old_aspect: Option<Aspect>,
     must_refresh: bool,
+    modal_depth: usize,
 
     // input events
     input_events: VecDeque<InputEvent>,
@@ -47,6 +63,9 @@ pub struct IO {
     // renderer state
     buffer: Vec<u32>,
     swatch: Swatch,
+    color_map: ColorMap,
+    crt: Option<CrtConfig>,
+    crt_buffers: CrtBuffers,
     screen: RedrawTrackingScreen,  
 
     // evt loop default hooks
@@ -80,14 +99,18 @@ impl IO {
         let swatch = *rendering::DEFAULT_SWATCH;
 
         IO { 
-            iteration: 0, tick: 0, last_tick_at: None, window_title, aspect_config,
+            iteration: 0, tick: 0, last_tick_at: None, clock: Rc::new(Clock::new()), 
+            ticks_per_second: DEFAULT_TICKS_PER_SECOND, input_per_second: DEFAULT_INPUT_PER_SECOND,
+            max_catch_up: None, dropped_ticks: 0,
+            window_title, aspect_config,
             
             window: None, keyboard: Keyboard::new(), mouse: Mouse::new(),
-            old_aspect: None, must_refresh: true,
+            old_aspect: None, must_refresh: true, modal_depth: 0,
 
             input_events: VecDeque::new(),
             
-            buffer: vec![], swatch, screen: RedrawTrackingScreen::new(swatch.default_bg, swatch.default_fg),
+            buffer: vec![], swatch, color_map: ColorMap::identity(), 
+            crt: None, crt_buffers: CrtBuffers::new(), screen: RedrawTrackingScreen::new(swatch.default_bg, swatch.default_fg),
             default_on_exit,
         }
     }
@@ -108,7 +131,7 @@ impl IO {
             panic!("{}", e); // TODO: Handle some errors
         });
         window.set_background_color(0, 0, 0); // TODO:
-        window.limit_update_rate(Some(std::time::Duration::from_micros(HANDLE_INPUT_EVERY as u64)));
+        window.limit_update_rate(Some(interval(self.input_per_second)));
         self.keyboard.monitor_minifb_utf32(&mut window);
         self.window = Some(window);
     }
@@ -128,6 +151,96 @@ impl IO {
         aspect
     }
 
+    // applied to the whole screen at render time: use ColorMap::identity() to turn it off
+    pub fn set_color_map(&mut self, color_map: ColorMap) {
+        if self.color_map != color_map {
+            self.color_map = color_map;
+            self.must_refresh = true;
+        }
+    }
+
+    /// Like Screen::to_ansi, but in the colors the window is actually using.
+    pub fn to_ansi(&self) -> String {
+        self.screen.target().to_ansi_with(|c| self.swatch.get(self.color_map.get(c)))
+    }
+
+    /// The cells that changed on screen in the last frame. Empty if nothing did.
+    /// Only informational: minifb has no partial upload, so the whole buffer still goes to the window.
+    pub fn last_damage(&self) -> &[CellRect] {
+        self.screen.last_damage()
+    }
+
+    /// Split full redraws across this many threads. Only worth it for big terminals.
+    pub fn set_render_threads(&mut self, threads: usize) {
+        self.screen.threads = threads.max(1);
+    }
+
+    // off (None) by default
+    pub fn set_crt(&mut self, crt: Option<CrtConfig>) {
+        if self.crt != crt {
+            self.crt = crt;
+            self.must_refresh = true;
+        }
+    }
+
+    pub fn ticks_per_second(&self) -> u32 {
+        self.ticks_per_second
+    }
+
+    /// How many ticks `time` lasts at the current tick rate, rounded up.
+    pub fn ticks_in(&self, time: Duration) -> u64 {
+        ticks_in(time, self.ticks_per_second)
+    }
+
+    // 30 by default
+    pub fn set_tick_rate(&mut self, ticks_per_second: u32) {
+        self.ticks_per_second = ticks_per_second.max(1);
+        self.mouse.hover.set_tick_rate(self.ticks_per_second);
+    }
+
+    // how often to poll for input. 240 by default
+    pub fn set_input_rate(&mut self, per_second: u32) {
+        self.input_per_second = per_second.max(1);
+        if let Some(win) = self.window.as_mut() {
+            win.limit_update_rate(Some(interval(self.input_per_second)));
+        }
+    }
+
+    /// Off (None) by default: if a frame runs long, the ticks it missed are skipped.
+    /// With Some(n), missed ticks get delivered late, up to n at once, so logic keeps up with
+    /// real time. Any more than n are dropped and counted in dropped_ticks().
+    pub fn set_fixed_timestep(&mut self, max_catch_up: Option<u32>) {
+        self.max_catch_up = max_catch_up.map(|n| n.max(1));
+    }
+
+    pub fn dropped_ticks(&self) -> u64 {
+        self.dropped_ticks
+    }
+
+    // how long the pointer has to rest on something before HoverHold. half a second by default
+    pub fn set_hover_delay(&mut self, delay: Duration) {
+        self.mouse.hover.set_hold(delay, self.ticks_per_second);
+    }
+
+    // off by default: numpad keys come in as the digits and symbols printed on them
+    pub fn set_distinguish_numpad(&mut self, distinguish: bool) {
+        self.keyboard.distinguish_numpad = distinguish;
+    }
+
+    // how close together clicks have to be to count as a double or triple click
+    pub fn set_multi_click_interval(&mut self, interval: Duration) {
+        self.mouse.multi_click.interval = interval;
+    }
+
+    /// Starts now and runs for `ticks` ticks. While any tween you still hold is running, on_redraw gets called every tick.
+    pub fn tween(&self, ticks: u64, easing: Easing) -> Tween {
+        Tween::new(self.clock.clone(), ticks, easing)
+    }
+
+    pub fn animating(&self) -> bool {
+        self.clock.is_busy()
+    }
+
     pub fn getch(&mut self, mut on_redraw: impl FnMut(&Screen)) -> KeyEvent {
         let mut inp = None;
         self.wait(EventLoop {
@@ -165,11 +278,14 @@ impl IO {
     }
 
     fn low_level_menu(&mut self, menu: Menu, on_redraw: &mut impl FnMut(&Screen, Menu)) -> Signal {
+        menu.set_tick_rate(self.ticks_per_second);
         let mut cmd = None;
         self.wait(EventLoop {
             on_redraw: Box::new(|io| { 
                 menu.clear();
-                on_redraw(io.screen.target(), menu.share()) 
+                on_redraw(io.screen.target(), menu.share());
+                menu.draw_tooltip(io.screen.target());
+                menu.draw_drag_ghost(io.screen.target());
             }),
             on_exit: Box::new(self.default_on_exit),
 
@@ -184,20 +300,17 @@ impl IO {
 
     pub fn sleep(&mut self, time: f64, mut on_redraw: impl FnMut(&Screen)) {
         // TODO: Clear clicks and keys if we're sleeping
-        let mut tick = 0;
+        // wall clock, not ticks -- ticks can be skipped or change rate
+        let start = Instant::now();
         self.wait(EventLoop {
             on_redraw: Box::new(|io| on_redraw(io.screen.target())),
             on_exit: Box::new(self.default_on_exit),
 
             on_input: Box::new(|_, i| {
-                match i {
-                    InputEvent::Tick(_) => {
-                        if tick as f64 / TICKS_PER_SECOND as f64 > time {
-                            return Resume::PopEvtLoop;
-                        }
-                        tick += 1;
+                if let InputEvent::Tick(_) = i {
+                    if start.elapsed().as_secs_f64() >= time {
+                        return Resume::PopEvtLoop;
                     }
-                    _ => {}
                 }
                 Resume::NotYet
             }),
@@ -235,32 +348,45 @@ impl IO {
             }
 
             // physically redraw if needed
-            let needs_physical_redraw = aspect_changed || window_changed || needs_virtual_redraw || self.mouse.interactor_changed();
+            // animated cells move on their own: draw() works out if the tick actually changed anything
+            let needs_physical_redraw = 
+                aspect_changed || window_changed || needs_virtual_redraw || 
+                self.mouse.interactor_changed() || self.screen.target().is_animated();
             if needs_physical_redraw {
                 let touched = self.draw(aspect, self.mouse.interactor());
 
                 let win = self.window.as_mut().unwrap();
                 if touched {
+                    let (w, h) = (aspect.buf_size.width as usize, aspect.buf_size.height as usize);
+                    let out = if let Some(crt) = &self.crt {
+                        self.crt_buffers.apply(crt, &self.buffer, w, h);
+                        &self.crt_buffers.out
+                    } else {
+                        &self.buffer
+                    };
                     // We unwrap here as we want this code to exit if it fails. Real applications may want to handle this in a different way
                     win
-                        .update_with_buffer(&self.buffer, aspect.buf_size.width as usize, aspect.buf_size.height as usize)
+                        .update_with_buffer(out, w, h)
                         .unwrap();
                 } else {
                     win.update()
                 }
             } else {
+                self.screen.clear_damage();
                 let win = self.window.as_mut().unwrap();
                 win.update()
             }
 
             // check events, starting with ticks
-            let now = Instant::now();
-            let is_new_tick = if let Some(lfa) = self.last_tick_at {
-                now.duration_since(lfa).as_micros() > APPARENT_TICK_MICROSECONDS
-            } else { true };
+            let new_ticks = self.due_ticks(Instant::now());
+            let is_new_tick = new_ticks > 0;
+            let first_new_tick = self.tick + 1;
             if is_new_tick {
-                self.tick += 1;
-                self.last_tick_at = Some(now);
+                self.tick += new_ticks;
+
+                // tweens move on ticks, so redraw for them. this includes the tick they finish on
+                self.clock.tick.set(self.tick);
+                if self.clock.is_busy() { self.must_refresh = true; }
             }
 
             // now keyboard etc
@@ -280,8 +406,8 @@ impl IO {
                 self.input_events.push_back(InputEvent::Mouse(mouse_evt));
             }
 
-            if is_new_tick {
-                self.input_events.push_back(InputEvent::Tick(self.tick));
+            for tick in first_new_tick..=self.tick {
+                self.input_events.push_back(InputEvent::Tick(tick));
             }
 
             while let Some(i_evt) = self.input_events.pop_front() {
@@ -295,15 +421,50 @@ impl IO {
         }
     }
 
+    // how many ticks to deliver this iteration
+    fn due_ticks(&mut self, now: Instant) -> u64 {
+        let period = interval(self.ticks_per_second);
+        let last = match self.last_tick_at {
+            None => { self.last_tick_at = Some(now); return 1 }
+            Some(l) => l,
+        };
+        let elapsed = now.duration_since(last);
+
+        match self.max_catch_up {
+            None => {
+                if elapsed > period { self.last_tick_at = Some(now); 1 } else { 0 }
+            }
+            Some(max) => {
+                // stay on the grid of tick times, even for ticks we drop
+                let due = (elapsed.as_micros() / period.as_micros().max(1)) as u64;
+                self.last_tick_at = Some(last + period * due as u32);
+                if due > max as u64 { self.dropped_ticks += due - max as u64 }
+                due.min(max as u64)
+            }
+        }
+    }
+
     // bool: "was it touched?"
     fn draw(&mut self, aspect: Aspect, interactor: Interactor) -> bool {
         self.screen.draw(
             Render { 
                 aspect, 
                 swatch: self.swatch,
+                color_map: self.color_map,
                 interactor,
+                tick: self.tick,
             }, 
             &mut self.buffer
         )
     }
 }
+
+fn interval(per_second: u32) -> Duration {
+    Duration::from_micros(1_000_000 / per_second.max(1) as u64)
+}
+
+// for delays that should last the same time whatever the tick rate is. rounds up
+pub(crate) fn ticks_in(time: Duration, ticks_per_second: u32) -> u64 {
+    let micros = time.as_micros() * ticks_per_second as u128;
+    micros.div_ceil(1_000_000) as u64
+}
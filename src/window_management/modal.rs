@@ -0,0 +1,69 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{IO, Menu, Screen, Signal, drawing::Snapshot};
+
+// A modal is a menu that ends with a value when something calls Resolve::with,
+// or with None if a handler breaks without one. To nest them, return Signal::Modal from inside one and call io.modal() again.
+type OnRedraw<'a, T> = Box<dyn 'a+FnMut(&Screen, Menu, &Resolve<T>)>;
+
+pub struct Modal<'a, T> {
+    on_redraw: OnRedraw<'a, T>,
+    underneath: Option<u8>,  // None: blank. Some(n): the screen we came from, darkened n steps
+}
+
+impl<'a, T> Modal<'a, T> {
+    pub fn new(on_redraw: impl 'a+FnMut(&Screen, Menu, &Resolve<T>)) -> Modal<'a, T> {
+        Modal { on_redraw: Box::new(on_redraw), underneath: None }
+    }
+
+    /// Draw the screen that opened this modal underneath it. (Its buttons won't work.)
+    pub fn underneath(self) -> Self {
+        self.dimmed(0)
+    }
+
+    pub fn dimmed(mut self, steps: u8) -> Self {
+        self.underneath = Some(steps);
+        self
+    }
+}
+
+// hand a clone of this to each handler that can close the modal
+pub struct Resolve<T>(Rc<Cell<Option<T>>>);
+
+impl<T> Resolve<T> {
+    pub fn share(&self) -> Resolve<T> {
+        Resolve(self.0.clone())
+    }
+
+    /// Close the modal, returning Some(`value`) from IO::modal.
+    pub fn with(&self, value: T) -> Signal {
+        self.0.set(Some(value));
+        Signal::Break
+    }
+}
+
+impl IO {
+    /// None if the modal closed without anything calling Resolve::with.
+    pub fn modal<T>(&mut self, mut modal: Modal<T>) -> Option<T> {
+        let under: Option<(Snapshot, u8)> = modal.underneath.map(|dim| (self.screen.target().snapshot(), dim));
+        let resolve = Resolve(Rc::new(Cell::new(None)));
+
+        self.modal_depth += 1;
+        // IO::menu refreshes on the way out, so the screen below comes back by itself
+        self.must_refresh = true;
+        self.menu(|out, menu| {
+            if let Some((snapshot, dim)) = &under {
+                out.restore(snapshot);
+                if *dim > 0 { out.brush().dim(*dim) }
+            }
+            (modal.on_redraw)(out, menu, &resolve)
+        });
+        self.modal_depth -= 1;
+        resolve.0.take()
+    }
+
+    /// How many modals are open right now.
+    pub fn modal_depth(&self) -> usize {
+        self.modal_depth
+    }
+}
@@ -2,12 +2,14 @@ use std::collections::VecDeque;
 
 use crate::aliases::*;
 use crate::Interactor;
-use super::{MouseButton, MouseEvent};
+use super::{Modifiers, MouseButton, MouseEvent};
 
 #[derive(Clone, Copy)]
 pub struct DragMonitor {
     start: Option<State>,
+    start_interactor: Interactor,  // whatever got the click, even if the screen changes under it
     old: Option<State>,
+    moved: bool,
     event_to_send: Option<ToSend>, 
 }
 
@@ -27,14 +29,18 @@ impl DragMonitor {
     pub fn new() -> DragMonitor {
         DragMonitor {
             start: None,
+            start_interactor: Interactor::none(),
             old: None,
+            moved: false,
             event_to_send: None,  // use this to rate-limit
         }
     }
 
-    pub(crate) fn down(&mut self, point: CellPoint) {
+    pub(crate) fn down(&mut self, point: CellPoint, interactor: Interactor) {
         self.start = Some(State { point });
-        self.old = self.start
+        self.start_interactor = interactor;
+        self.old = self.start;
+        self.moved = false;
     }
 
     pub(crate) fn at(
@@ -47,6 +53,7 @@ impl DragMonitor {
         let new = State { point };
 
         if old.point == new.point { return }
+        self.moved = true;
 
         if let Some(e) = &mut self.event_to_send {
             e.now = new.point;
@@ -60,10 +67,11 @@ impl DragMonitor {
         &mut self,
         events: &mut VecDeque<crate::MouseEvent>, 
         mouse_button: MouseButton,
+        modifiers: Modifiers,
         interactor: &impl Fn(CellPoint) -> Interactor,
     ) {
         if let Some(ToSend { start, last, now }) = self.event_to_send.take() {
-            let start_interactor = interactor(start);
+            let start_interactor = self.start_interactor;
             let last_interactor = interactor(last);
             let now_interactor = interactor(now);
 
@@ -74,7 +82,8 @@ impl DragMonitor {
                 last_point: last,
                 last_interactor: last_interactor,
                 now_point: now,
-                now_interactor: now_interactor
+                now_interactor,
+                modifiers,
             });
         }
     }
@@ -83,12 +92,26 @@ impl DragMonitor {
         &mut self,
         events: &mut VecDeque<crate::MouseEvent>,
         mouse_button: MouseButton,
+        modifiers: Modifiers,
         interactor: &impl Fn(CellPoint) -> Interactor,
     ) {
-        self.post_events(events, mouse_button, interactor);
+        self.post_events(events, mouse_button, modifiers, interactor);
+
+        if let (true, Some(start), Some(now)) = (self.moved, self.start, self.old) {
+            events.push_back(MouseEvent::DragEnd {
+                mouse_button,
+                start_point: start.point,
+                start_interactor: self.start_interactor,
+                now_point: now.point,
+                now_interactor: interactor(now.point),
+                modifiers,
+            });
+        }
 
         self.event_to_send = None;
+        self.moved = false;
         self.start = None;
+        self.start_interactor = Interactor::none();
         self.old = None;
     }
 }
@@ -0,0 +1,71 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::aliases::*;
+use crate::Interactor;
+use crate::window_management::{DEFAULT_TICKS_PER_SECOND, ticks_in};
+use super::MouseEvent;
+
+const DEFAULT_HOLD: Duration = Duration::from_millis(500);
+
+pub struct HoverMonitor {
+    interactor: Interactor,
+    point: CellPoint,
+    ticks: u64,
+    hold: Duration,
+    hold_ticks: u64,  // hold, at the current tick rate
+}
+
+impl HoverMonitor {
+    pub fn new() -> HoverMonitor {
+        HoverMonitor { 
+            interactor: Interactor::none(), point: point2(0, 0), ticks: 0, 
+            hold: DEFAULT_HOLD, hold_ticks: ticks_in(DEFAULT_HOLD, DEFAULT_TICKS_PER_SECOND),
+        }
+    }
+
+    pub(crate) fn set_hold(&mut self, hold: Duration, ticks_per_second: u32) {
+        self.hold = hold;
+        self.hold_ticks = ticks_in(hold, ticks_per_second);
+    }
+
+    pub(crate) fn set_tick_rate(&mut self, ticks_per_second: u32) {
+        self.set_hold(self.hold, ticks_per_second)
+    }
+
+    pub(crate) fn at(
+        &mut self,
+        events: &mut VecDeque<MouseEvent>,
+        point: CellPoint,
+        interactor: Interactor,
+        new_tick: bool,
+    ) {
+        self.point = point;
+        if interactor != self.interactor {
+            if self.interactor != Interactor::none() {
+                events.push_back(MouseEvent::HoverLeave(point, self.interactor));
+            }
+            if interactor != Interactor::none() {
+                events.push_back(MouseEvent::HoverEnter(point, interactor));
+            }
+            self.interactor = interactor;
+            self.ticks = 0;
+            return
+        }
+
+        if !new_tick || interactor == Interactor::none() { return }
+        self.ticks += 1;
+        // only once per visit
+        if self.ticks == self.hold_ticks.max(1) {
+            events.push_back(MouseEvent::HoverHold(point, interactor));
+        }
+    }
+
+    // the pointer left the window, so there's no new position to compare against
+    pub(crate) fn gone(&mut self, events: &mut VecDeque<MouseEvent>) {
+        if self.interactor != Interactor::none() {
+            events.push_back(MouseEvent::HoverLeave(self.point, self.interactor));
+        }
+        self.interactor = Interactor::none();
+        self.ticks = 0;
+    }
+}
@@ -1,4 +1,6 @@
 mod drag;
+mod hover;
+mod multi_click;
 mod scroll_wheel;
 mod wiggle;
 
@@ -10,9 +12,9 @@ use minifb::{MouseButton as MinifbMouseButton, MouseMode, Window};
 
 use crate::{aliases::CellPoint, rendering::Interactor};
 
-use self::{scroll_wheel::ScrollWheelMonitor, wiggle::WiggleMonitor};
+use self::{hover::HoverMonitor, multi_click::MultiClickMonitor, scroll_wheel::ScrollWheelMonitor, wiggle::WiggleMonitor};
 
-use super::{Aspect, input::MouseEvent, input::MouseButton};
+use super::{Aspect, keyboard, input::Modifiers, input::MouseEvent, input::MouseButton};
 
 use drag::DragMonitor;
 
@@ -21,6 +23,8 @@ pub(crate) struct Mouse {
     drag: EnumMap<MouseButton, DragMonitor>,
     scroll_wheel: ScrollWheelMonitor,
     wiggle: WiggleMonitor,
+    pub hover: HoverMonitor,
+    pub multi_click: MultiClickMonitor,
 
     old: Option<State>,
     new: Option<State>,
@@ -31,6 +35,7 @@ pub(crate) struct Mouse {
 #[derive(Clone, Copy)]
 struct State {
     down: EnumMap<MouseButton, bool>,
+    modifiers: Modifiers,
 
     cell_xy: CellPoint,
     interactor: Interactor,
@@ -45,6 +50,8 @@ impl Mouse {
             },
             scroll_wheel: ScrollWheelMonitor::new(),
             wiggle: WiggleMonitor::new(),
+            hover: HoverMonitor::new(),
+            multi_click: MultiClickMonitor::new(),
             old: None, 
             new: None,
             events: VecDeque::new(),
@@ -60,7 +67,8 @@ impl Mouse {
         let current_state = Mouse::current_state(aspect, window, &any_interactor);
 
         if let None = current_state {
-            // don't bother generating events for now
+            // don't bother generating events for now, except to take down tooltips
+            self.hover.gone(&mut self.events);
             return;
         }
 
@@ -75,28 +83,35 @@ impl Mouse {
             (Some(_), None) => {}
             (Some(old), Some(new)) => {
                 if let Some((_, scroll_y)) = window.get_scroll_wheel() {
-                    self.scroll_wheel.at(&mut self.events, new.cell_xy, scroll_y, &|p| any_interactor(p).1);
+                    self.scroll_wheel.at(&mut self.events, new.cell_xy, scroll_y, new.modifiers, &|p| any_interactor(p).1);
                 }
 
                 for mb in MouseButton::ALL {
                     if new.down[mb] && !old.down[mb] {
-                        self.events.push_back(Click(mb, new.cell_xy, new.interactor));
-                        self.drag[mb].down(new.cell_xy);
+                        let mods = new.modifiers;
+                        self.events.push_back(Click(mb, new.cell_xy, new.interactor, mods));
+                        match self.multi_click.down(mb, new.cell_xy) {
+                            2 => self.events.push_back(DoubleClick(mb, new.cell_xy, new.interactor, mods)),
+                            3 => self.events.push_back(TripleClick(mb, new.cell_xy, new.interactor, mods)),
+                            _ => {}
+                        }
+                        self.drag[mb].down(new.cell_xy, new.interactor);
                     }
 
                     self.drag[mb].at(new.cell_xy);
 
                     if !new.down[mb] && old.down[mb] {
-                        self.events.push_back(Up(mb, new.cell_xy, new.interactor));
-                        self.drag[mb].up(&mut self.events, mb, &|p| any_interactor(p).0)  // TODO: Maybe just do this whenever !new.down?
+                        self.events.push_back(Up(mb, new.cell_xy, new.interactor, new.modifiers));
+                        self.drag[mb].up(&mut self.events, mb, new.modifiers, &|p| any_interactor(p).0)  // TODO: Maybe just do this whenever !new.down?
                     }
 
                     if new_tick {
-                        self.drag[mb].post_events(&mut self.events, mb, &|p| any_interactor(p).0)
+                        self.drag[mb].post_events(&mut self.events, mb, new.modifiers, &|p| any_interactor(p).0)
                     }
                 }
                 self.wiggle.at(new.cell_xy);
                 self.wiggle.post_events(&mut self.events, &|p| any_interactor(p).0);
+                self.hover.at(&mut self.events, new.cell_xy, new.interactor, new_tick);
             }
         }
     }
@@ -119,8 +134,10 @@ impl Mouse {
         Some(State { 
             down: enum_map::enum_map![
                 MouseButton::Left => window.get_mouse_down(MinifbMouseButton::Left),
+                MouseButton::Middle => window.get_mouse_down(MinifbMouseButton::Middle),
                 MouseButton::Right => window.get_mouse_down(MinifbMouseButton::Right),
             ],
+            modifiers: keyboard::modifiers(window),
             cell_xy,
             interactor: interactors.0,
         })
@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+use enum_map::EnumMap;
+
+use crate::aliases::*;
+use super::MouseButton;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(400);
+
+pub struct MultiClickMonitor {
+    last: EnumMap<MouseButton, Option<(Instant, CellPoint, u8)>>,
+    pub interval: Duration,
+}
+
+impl MultiClickMonitor {
+    pub fn new() -> MultiClickMonitor {
+        MultiClickMonitor { last: enum_map::enum_map! { _ => None }, interval: DEFAULT_INTERVAL }
+    }
+
+    // 1, 2 or 3. a fourth click quickly after a triple starts over
+    pub(crate) fn down(&mut self, mouse_button: MouseButton, point: CellPoint) -> u8 {
+        let now = Instant::now();
+        let count = match self.last[mouse_button] {
+            Some((at, p, n)) if p == point && n < 3 && now.duration_since(at) <= self.interval => n + 1,
+            _ => 1,
+        };
+        self.last[mouse_button] = Some((now, point, count));
+        count
+    }
+}
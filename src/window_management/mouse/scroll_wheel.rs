@@ -2,6 +2,8 @@ use std::collections::VecDeque;
 
 use crate::{CellPoint, Interactor, MouseEvent};
 
+use super::Modifiers;
+
 pub struct ScrollWheelMonitor {
 }
 
@@ -15,9 +17,10 @@ impl ScrollWheelMonitor {
         events: &mut VecDeque<crate::MouseEvent>, 
         point: CellPoint, 
         scroll_y: f32,
+        modifiers: Modifiers,
         interactor: &impl Fn(CellPoint) -> Interactor,
     ) {
         // NOTE: Currently scroll_y is always divisible by 12
-        events.push_back(MouseEvent::Scroll(-scroll_y / 12.0, point, interactor(point)));
+        events.push_back(MouseEvent::Scroll(-scroll_y / 12.0, point, interactor(point), modifiers));
     }
 }
@@ -7,11 +7,41 @@ pub struct OnKey {
     keycode: Keycode,
     control: bool,
     shift: bool,
+    alt: bool,
+    super_key: bool,
 }
 
 impl OnKey {
     pub fn only(keycode: Keycode) -> OnKey {
-        OnKey { keycode, control: false, shift: false, }
+        OnKey { keycode, control: false, shift: false, alt: false, super_key: false }
+    }
+
+    pub fn combo(combo: KeyCombo) -> OnKey {
+        OnKey { 
+            keycode: combo.code, 
+            control: combo.control, shift: combo.shift, 
+            alt: combo.alt, super_key: combo.super_key,
+        }
+    }
+
+    pub fn control(mut self) -> OnKey {
+        self.control = true;
+        self
+    }
+
+    pub fn shift(mut self) -> OnKey {
+        self.shift = true;
+        self
+    }
+
+    pub fn alt(mut self) -> OnKey {
+        self.alt = true;
+        self
+    }
+
+    pub fn super_key(mut self) -> OnKey {
+        self.super_key = true;
+        self
     }
 
     fn match_for(&self, combo: Option<KeyCombo>) -> bool {
@@ -20,7 +50,9 @@ impl OnKey {
 
         self.keycode == combo.code && 
         self.control == combo.control &&
-        self.shift == combo.shift
+        self.shift == combo.shift &&
+        self.alt == combo.alt &&
+        self.super_key == combo.super_key
     }
 
     pub fn up_or_down(self) -> KeyRecognizer<'static> {
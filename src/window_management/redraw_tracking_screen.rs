@@ -1,6 +1,8 @@
 use std::mem;
 
-use crate::{CellSize, Screen, constants::{CELL_X, CELL_Y}, rendering::Render};
+use crate::{CellRect, CellSize, Screen, constants::{CELL_X, CELL_Y}, rendering::{Render, rasterize}};
+
+use super::damage::Damage;
 
 use gridd_euclid::PointsIn;
 
@@ -11,6 +13,9 @@ pub(crate) struct RedrawTrackingScreen {
 
     last_frame_drawn: u64,
     last_render: Option<Render>,
+    last_damage: Vec<CellRect>,
+
+    pub threads: usize,
 }
 
 impl RedrawTrackingScreen {
@@ -22,6 +27,9 @@ impl RedrawTrackingScreen {
 
             last_frame_drawn: 0,
             last_render: None,
+            last_damage: vec![],
+
+            threads: 1,
         }
     }
 
@@ -41,81 +49,111 @@ impl RedrawTrackingScreen {
         self.new.resize(size);  
     }
 
+    // the cells physically redrawn by the last call to draw()
+    pub(crate) fn last_damage(&self) -> &[CellRect] {
+        &self.last_damage
+    }
+
+    // for frames where draw() doesn't get called at all
+    pub(crate) fn clear_damage(&mut self) {
+        self.last_damage.clear();
+    }
+
     fn old_frame(&self) -> u64 {
         self.new_frame - 1
     }
 
     // bool: whether any changes were made
-    pub fn draw(&mut self, render: Render, buffer: &mut Vec<u32>) -> bool {
+    pub fn draw(&mut self, render: Render, buffer: &mut [u32]) -> bool {
         let new_render = render;
 
         let (last_screen, new_screen) = if self.last_frame_drawn == self.old_frame() {
             (&self.old, &self.new)
         } else if self.last_frame_drawn == self.new_frame {
             if self.last_render.as_ref() == Some(&new_render) {
+                self.last_damage.clear();
                 return false;
             }
             (&self.new, &self.new)
         } else {
-            self.completely_redraw(buffer, &new_render, &self.new);
+            self.last_damage = self.completely_redraw(buffer, &new_render, &self.new);
             self.last_frame_drawn = self.new_frame;
             self.last_render.replace(new_render);
             return true;
         };
 
-        let touched  = match &self.last_render {
+        let damage = match &self.last_render {
             Some(last_render) if last_render.aspect == new_render.aspect => {
                 self.draw_differences(buffer, last_render, &last_screen, &new_render, new_screen)
             }
             _ => {
-                self.completely_redraw(buffer, &new_render, &self.new);
-                true
+                self.completely_redraw(buffer, &new_render, &self.new)
             }
         };
 
         self.last_frame_drawn = self.new_frame;
         self.last_render.replace(new_render);
-        return touched;
+        self.last_damage = damage;
+        !self.last_damage.is_empty()
     }
 
     fn draw_differences(
         &self, 
-        buffer: &mut Vec<u32>,
+        buffer: &mut [u32],
         last_render: &Render, last_screen: &Screen, new_render: &Render, new_screen: &Screen,
-    ) -> bool {
+    ) -> Vec<CellRect> {
         let screen_rect = new_screen.rect();
         let term_rect = new_render.aspect.term_rect();
         assert_eq!(screen_rect, term_rect.cast());
         assert_eq!(screen_rect.area() as usize * CELL_X * CELL_Y, buffer.len());
 
-        let mut touched = false;
+        // When only the mouseover or the tick changed, only cells belonging to the
+        // old or new interactor, or animated cells, can look different. Those are cheap to find
+        // without rendering
+        let same_screen = 
+            std::ptr::eq(last_screen, new_screen) &&
+            last_render.swatch == new_render.swatch &&
+            last_render.color_map == new_render.color_map;
+        let tick_changed = last_render.tick != new_render.tick;
+
+        let mut damage = Damage::new();
         for term_xy in u16::points_in(term_rect) {
+            if same_screen {
+                let here = new_screen.cells.get(term_xy.cast()).unwrap().get();
+                let interactor = here.interactor.interactor;
+                let maybe_moved = 
+                    interactor == last_render.interactor || interactor == new_render.interactor ||
+                    (tick_changed && here.animation.is_some());
+                if !maybe_moved { continue }
+            }
+
             let old_content = last_render.get_content(last_screen, term_xy.cast());
             let new_content = new_render.get_content(new_screen, term_xy.cast());
             if old_content != new_content {
-                new_content.physically_draw(
+                new_content.draw_cell(
                     buffer, term_xy.x, term_xy.y, new_render.aspect.term_size.width, 
                 );
-                touched = true
+                damage.touch(term_xy.cast());
             }
         }
-        touched
+        damage.finish()
     }
 
     fn completely_redraw(
         &self, 
-        buffer: &mut Vec<u32>, 
+        buffer: &mut [u32], 
         render: &Render, screen: &Screen
-    ) {
+    ) -> Vec<CellRect> {
         let screen_rect = screen.rect();
         let term_rect = render.aspect.term_rect();
         assert_eq!(screen_rect, term_rect.cast());
         assert_eq!(screen_rect.area() as usize * CELL_X * CELL_Y, buffer.len());
 
-        for term_xy in u16::points_in(term_rect) {
-            render.get_content(screen, term_xy.cast()).physically_draw(
-                buffer, term_xy.x, term_xy.y, render.aspect.term_size.width, 
-            );
-        }
+        // Screen isn't Sync, so work out what goes in each cell first, then rasterize 
+        let contents: Vec<_> = u16::points_in(term_rect)
+            .map(|term_xy| render.get_content(screen, term_xy.cast()))
+            .collect();
+        rasterize(&contents, render.aspect.term_size.width as usize, buffer, self.threads);
+        vec![screen_rect]
     }
 }
\ No newline at end of file
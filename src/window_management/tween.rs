@@ -0,0 +1,207 @@
+use std::{cell::{Cell, RefCell}, f32::consts::PI, rc::{Rc, Weak}};
+
+use euclid::{point2, size2, vec2};
+
+use crate::aliases::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    QuadIn, QuadOut, QuadInOut,
+    CubicOut,
+    BackOut,  // overshoots, then settles
+    Bounce,
+    Elastic,
+}
+
+impl Easing {
+    /// `t` goes from 0 to 1. So does the result, but BackOut and Elastic go a little past 1 on the way.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::BackOut => {
+                let c1 = 1.70158;
+                1.0 + (c1 + 1.0) * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+            Easing::Bounce => {
+                let (n, d) = (7.5625, 2.75);
+                if t < 1.0 / d { n * t * t }
+                else if t < 2.0 / d { let t = t - 1.5 / d; n * t * t + 0.75 }
+                else if t < 2.5 / d { let t = t - 2.25 / d; n * t * t + 0.9375 }
+                else { let t = t - 2.625 / d; n * t * t + 0.984375 }
+            }
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 { return t }
+                2f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0
+            }
+        }
+    }
+}
+
+// IO and every Tween it hands out share one of these. Tweens read the tick
+// from it, and IO asks it whether any live tween is still moving, to know if it has to
+// keep calling on_redraw
+pub(crate) struct Clock {
+    pub tick: Cell<u64>,
+    ends: RefCell<Vec<Weak<Cell<u64>>>>,  // one per live tween. dropped tweens stop counting
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock { tick: Cell::new(0), ends: RefCell::new(vec![]) }
+    }
+
+    fn track(&self) -> Rc<Cell<u64>> {
+        let end = Rc::new(Cell::new(0));
+        self.ends.borrow_mut().push(Rc::downgrade(&end));
+        end
+    }
+
+    pub fn is_busy(&self) -> bool {
+        let tick = self.tick.get();
+        let mut ends = self.ends.borrow_mut();
+        ends.retain(|e| e.strong_count() > 0);
+        // inclusive, so the tween's last frame gets drawn too
+        ends.iter().any(|e| e.upgrade().is_some_and(|e| tick <= e.get()))
+    }
+}
+
+// Get one from IO::tween, then read it from on_redraw. Dropping it stops the redraws
+pub struct Tween {
+    clock: Rc<Clock>,
+    start: u64,
+    duration: u64,  // in ticks
+    easing: Easing,
+    end: Rc<Cell<u64>>,  // what the clock sees
+}
+
+impl Clone for Tween {
+    fn clone(&self) -> Tween {
+        let end = self.clock.track();
+        end.set(self.end.get());
+        Tween { clock: self.clock.clone(), start: self.start, duration: self.duration, easing: self.easing, end }
+    }
+}
+
+impl Tween {
+    pub(crate) fn new(clock: Rc<Clock>, duration: u64, easing: Easing) -> Tween {
+        let end = clock.track();
+        let mut tween = Tween { clock, start: 0, duration, easing, end };
+        tween.restart();
+        tween
+    }
+
+    pub fn restart(&mut self) {
+        self.start = self.clock.tick.get();
+        self.end.set(self.start + self.duration);
+    }
+
+    /// Skip to the end, so the screen doesn't keep refreshing for it.
+    pub fn finish(&mut self) {
+        self.start = self.clock.tick.get().saturating_sub(self.duration);
+        self.end.set(self.start + self.duration);
+    }
+
+    // 0 to 1, before easing
+    fn elapsed(&self) -> f32 {
+        if self.duration == 0 { return 1.0 }
+        (self.clock.tick.get().saturating_sub(self.start) as f32 / self.duration as f32).min(1.0)
+    }
+
+    pub fn done(&self) -> bool {
+        self.clock.tick.get() >= self.start + self.duration
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.easing.apply(self.elapsed())
+    }
+
+    pub fn lerp(&self, from: f32, to: f32) -> f32 {
+        from + (to - from) * self.progress()
+    }
+
+    pub fn lerp_isize(&self, from: isize, to: isize) -> isize {
+        self.lerp(from as f32, to as f32).round() as isize
+    }
+
+    pub fn lerp_vector(&self, from: CellVector, to: CellVector) -> CellVector {
+        vec2(self.lerp_isize(from.x, to.x), self.lerp_isize(from.y, to.y))
+    }
+
+    pub fn lerp_point(&self, from: CellPoint, to: CellPoint) -> CellPoint {
+        point2(self.lerp_isize(from.x, to.x), self.lerp_isize(from.y, to.y))
+    }
+
+    pub fn lerp_size(&self, from: CellSize, to: CellSize) -> CellSize {
+        size2(self.lerp_isize(from.width, to.width).max(0), self.lerp_isize(from.height, to.height).max(0))
+    }
+
+    pub fn lerp_rect(&self, from: CellRect, to: CellRect) -> CellRect {
+        CellRect::new(self.lerp_point(from.origin, to.origin), self.lerp_size(from.size, to.size))
+    }
+
+    /// Walk along `ramp` (for instance `colors::LtRed`) from its first entry to its last.
+    pub fn lerp_color(&self, ramp: &[u8]) -> u8 {
+        if ramp.is_empty() { return 0 }
+        let last = ramp.len() as isize - 1;
+        ramp[self.lerp_isize(0, last).clamp(0, last) as usize]
+    }
+
+    /// Jitters up to `magnitude` cells in each direction, dying down as the tween finishes.
+    /// Pass it to Brush::offset_rect.
+    pub fn shake(&self, magnitude: isize) -> CellVector {
+        if self.done() { return vec2(0, 0) }
+        let amount = ((1.0 - self.elapsed()) * magnitude.abs() as f32).round() as isize;
+        if amount == 0 { return vec2(0, 0) }
+
+        // cheap hash of the tick so it doesn't look like a metronome
+        let h = self.clock.tick.get().wrapping_mul(0x9e3779b97f4a7c15) >> 32;
+        let pick = |bits: u64| (bits % (2 * amount as u64 + 1)) as isize - amount;
+        vec2(pick(h & 0xffff), pick(h >> 16))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_is_only_busy_for_live_tweens() {
+        let clock = Rc::new(Clock::new());
+        assert!(!clock.is_busy());
+
+        let long = Tween::new(clock.clone(), 100, Easing::Linear);
+        let mut short = Tween::new(clock.clone(), 10, Easing::Linear);
+        clock.tick.set(5);
+        assert!(clock.is_busy());
+
+        drop(long);
+        clock.tick.set(10);
+        assert!(clock.is_busy());  // short's last frame
+        clock.tick.set(11);
+        assert!(!clock.is_busy());
+
+        short.restart();
+        assert!(clock.is_busy());
+        short.finish();
+        clock.tick.set(12);
+        assert!(!clock.is_busy());
+        assert!(short.done());
+    }
+
+    #[test]
+    fn shake_takes_either_sign() {
+        let clock = Rc::new(Clock::new());
+        let tween = Tween::new(clock.clone(), 10, Easing::Linear);
+        for tick in 0..10 {
+            clock.tick.set(tick);
+            let v = tween.shake(-3);
+            assert!(v.x.abs() <= 3 && v.y.abs() <= 3);
+        }
+    }
+}